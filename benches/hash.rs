@@ -0,0 +1,38 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use file_rw::{FileReader, FileWriter};
+use rand::RngCore;
+use tempfile::tempdir;
+
+const KB: usize = 1024;
+const MB: usize = 1024 * KB;
+
+fn benchmark_hash(c: &mut Criterion) {
+    let num_bytes_arr = [64 * KB, MB, 64 * MB, 256 * MB];
+    let tempdir = tempdir().unwrap();
+    let test_file_path = tempdir.path().join("test_file");
+    let mut file_writer = FileWriter::open(&test_file_path).unwrap();
+    let mut benchmark_group = c.benchmark_group("benchmark_hash");
+
+    num_bytes_arr.iter().for_each(|num_bytes| {
+        let mut dataset = vec![0u8; *num_bytes];
+        rand::thread_rng().fill_bytes(&mut dataset);
+        file_writer.overwrite(&dataset).unwrap();
+        let file_reader = FileReader::open(&test_file_path).unwrap();
+        benchmark_group.throughput(Throughput::Bytes(*num_bytes as u64));
+
+        benchmark_group.bench_with_input(
+            BenchmarkId::new("sha3_256", num_bytes),
+            &file_reader,
+            |b, file_reader| b.iter(|| file_reader.hash()),
+        );
+        benchmark_group.bench_with_input(
+            BenchmarkId::new("blake3", num_bytes),
+            &file_reader,
+            |b, file_reader| b.iter(|| file_reader.blake3()),
+        );
+    });
+    benchmark_group.finish();
+}
+
+criterion_group!(benches, benchmark_hash);
+criterion_main!(benches);
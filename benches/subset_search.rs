@@ -22,24 +22,21 @@ fn gen_dataset(num_bytes: usize) -> Vec<u8> {
 
 fn gen_find_bytes_replace_n_triplets(num_searches: usize) -> Vec<(Vec<u8>, Vec<u8>, usize)> {
     let mut rng = rand::thread_rng();
-    let vals_container = (1..num_searches)
-        .into_iter()
+
+    (1..num_searches)
         .map(|_| {
             let find_bytes_range = rng.gen_range(1u8..10u8);
             let find_bytes_vec = (1u8..find_bytes_range)
-                .into_iter()
                 .map(|_| rng.gen::<u8>())
                 .collect::<Vec<u8>>();
             let replace_vec = (1u8..find_bytes_range)
-                .into_iter()
                 .map(|_| rng.gen::<u8>())
                 .collect::<Vec<u8>>();
 
             let n = rng.gen_range(0..find_bytes_range as usize);
             (find_bytes_vec, replace_vec, n)
         })
-        .collect::<Vec<(Vec<u8>, Vec<u8>, usize)>>();
-    vals_container
+        .collect::<Vec<(Vec<u8>, Vec<u8>, usize)>>()
 }
 
 const KB: usize = 1024;
@@ -0,0 +1,64 @@
+use crate::{FileReader, FileWriter};
+use rkyv::{
+    api::high::{HighSerializer, HighValidator},
+    bytecheck::CheckBytes,
+    rancor,
+    ser::allocator::ArenaHandle,
+    util::AlignedVec,
+    Archive, Archived, Portable, Serialize,
+};
+use std::{io, path::Path};
+
+/// Validates the archived root in the provided bytes and returns a reference to it.
+/// Fails with `InvalidData` if the bytes are not aligned for the archived type or fail validation.
+fn access<T: Archive>(bytes: &[u8]) -> io::Result<&Archived<T>>
+where
+    Archived<T>: Portable + for<'a> CheckBytes<HighValidator<'a, rancor::Error>>,
+{
+    let align = std::mem::align_of::<Archived<T>>();
+    if !(bytes.as_ptr() as usize).is_multiple_of(align) {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!(
+                "mapping is not aligned to {} bytes for the archived type",
+                align
+            ),
+        ));
+    }
+    rkyv::access::<Archived<T>, rancor::Error>(bytes)
+        .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))
+}
+
+impl<P: AsRef<Path> + Send + Sync> FileReader<P> {
+    /// Validates the file data as an rkyv archive of `T` and returns a reference to the archived value
+    /// directly inside the mmap, without copying or deserializing.
+    /// Memory maps are page-aligned, so the archive's alignment requirements are met for files written
+    /// by `FileWriter::write_archived`.
+    pub fn archived<T: Archive>(&self) -> io::Result<&Archived<T>>
+    where
+        Archived<T>: Portable + for<'a> CheckBytes<HighValidator<'a, rancor::Error>>,
+    {
+        access::<T>(self.as_slice())
+    }
+}
+
+impl<P: AsRef<Path> + Send + Sync> FileWriter<P> {
+    /// Overwrites the file with the rkyv archive of `value`, for zero-copy access with `FileReader::archived`.
+    pub fn write_archived<T>(&mut self, value: &T) -> io::Result<&Self>
+    where
+        T: for<'a> Serialize<HighSerializer<AlignedVec, ArenaHandle<'a>, rancor::Error>>,
+    {
+        let bytes = rkyv::to_bytes::<rancor::Error>(value)
+            .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?;
+        self.overwrite(&bytes)
+    }
+
+    /// Validates the file data as an rkyv archive of `T` and returns a reference to the archived value
+    /// directly inside the mmap, without copying or deserializing.
+    pub fn archived<T: Archive>(&self) -> io::Result<&Archived<T>>
+    where
+        Archived<T>: Portable + for<'a> CheckBytes<HighValidator<'a, rancor::Error>>,
+    {
+        access::<T>(self.bytes())
+    }
+}
@@ -0,0 +1,279 @@
+use crate::{FileReader, FileWriter};
+use std::{io, path::Path};
+
+/// The most bytes a LEB128 varint of a `u64` can take.
+const MAX_VARINT_LEN: usize = 10;
+
+/// Returns an `UnexpectedEof` error for a value of `len` bytes at `offset` past the end of `bytes_len`.
+fn out_of_bounds(offset: usize, len: usize, bytes_len: usize) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::UnexpectedEof,
+        format!(
+            "{} bytes at offset {} extend past the end of the data ({} bytes)",
+            len, offset, bytes_len
+        ),
+    )
+}
+
+/// Returns the `N` bytes at `offset`.
+fn array_at<const N: usize>(bytes: &[u8], offset: usize) -> io::Result<[u8; N]> {
+    offset
+        .checked_add(N)
+        .and_then(|end| bytes.get(offset..end))
+        .and_then(|slice| slice.try_into().ok())
+        .ok_or_else(|| out_of_bounds(offset, N, bytes.len()))
+}
+
+/// Returns the `N` bytes at `offset` for writing.
+fn array_at_mut<const N: usize>(bytes: &mut [u8], offset: usize) -> io::Result<&mut [u8; N]> {
+    let bytes_len = bytes.len();
+    offset
+        .checked_add(N)
+        .and_then(|end| bytes.get_mut(offset..end))
+        .and_then(|slice| slice.try_into().ok())
+        .ok_or_else(|| out_of_bounds(offset, N, bytes_len))
+}
+
+/// Decodes the LEB128 (protobuf) varint at `offset`, returning its value and encoded length.
+/// Fails with `UnexpectedEof` if the data ends mid-varint and with `InvalidData` if it overflows a `u64`.
+pub(crate) fn uvarint_at(bytes: &[u8], offset: usize) -> io::Result<(u64, usize)> {
+    let mut value = 0u64;
+    for len in 0..MAX_VARINT_LEN {
+        let byte = *bytes
+            .get(offset.saturating_add(len))
+            .ok_or_else(|| out_of_bounds(offset, len + 1, bytes.len()))?;
+        if len == MAX_VARINT_LEN - 1 && byte > 1 {
+            break;
+        }
+        value |= u64::from(byte & 0x7f) << (7 * len);
+        if byte & 0x80 == 0 {
+            return Ok((value, len + 1));
+        }
+    }
+    Err(io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("varint at offset {} overflows a u64", offset),
+    ))
+}
+
+/// Encodes `value` as a LEB128 varint, returning the buffer and the encoded length.
+pub(crate) fn encode_uvarint(mut value: u64) -> ([u8; MAX_VARINT_LEN], usize) {
+    let mut buf = [0; MAX_VARINT_LEN];
+    let mut len = 0;
+    loop {
+        let byte = (value & 0x7f) as u8;
+        value >>= 7;
+        if value == 0 {
+            buf[len] = byte;
+            return (buf, len + 1);
+        }
+        buf[len] = byte | 0x80;
+        len += 1;
+    }
+}
+
+/// Maps a signed value onto an unsigned one with zigzag encoding, as protobuf's `sint64` does.
+fn zigzag_encode(value: i64) -> u64 {
+    ((value << 1) ^ (value >> 63)) as u64
+}
+
+fn zigzag_decode(value: u64) -> i64 {
+    (value >> 1) as i64 ^ -((value & 1) as i64)
+}
+
+/// Writes `value` as a LEB128 varint at `offset`, returning the encoded length.
+fn write_uvarint(bytes: &mut [u8], offset: usize, value: u64) -> io::Result<usize> {
+    let (buf, len) = encode_uvarint(value);
+    let bytes_len = bytes.len();
+    offset
+        .checked_add(len)
+        .and_then(|end| bytes.get_mut(offset..end))
+        .ok_or_else(|| out_of_bounds(offset, len, bytes_len))?
+        .copy_from_slice(&buf[..len]);
+    Ok(len)
+}
+
+/// A cursor that reads binary values from a byte slice, advancing past each value it reads.
+/// Reads past the end of the data fail with `UnexpectedEof` and leave the position unchanged.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ByteCursor<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl<'a> ByteCursor<'a> {
+    /// Creates a cursor at the start of the provided bytes.
+    pub fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes, position: 0 }
+    }
+
+    /// Returns the offset of the next byte to be read.
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Moves the cursor to `position`, which may be past the end of the data.
+    pub fn set_position(&mut self, position: usize) {
+        self.position = position;
+    }
+
+    /// Returns the number of bytes left to read.
+    pub fn remaining(&self) -> usize {
+        self.bytes.len().saturating_sub(self.position)
+    }
+
+    /// Returns true if there are no bytes left to read.
+    pub fn is_empty(&self) -> bool {
+        self.remaining() == 0
+    }
+
+    /// Returns the next `len` bytes, borrowed from the underlying data.
+    pub fn read_bytes(&mut self, len: usize) -> io::Result<&'a [u8]> {
+        let bytes = self
+            .position
+            .checked_add(len)
+            .and_then(|end| self.bytes.get(self.position..end))
+            .ok_or_else(|| out_of_bounds(self.position, len, self.bytes.len()))?;
+        self.position += len;
+        Ok(bytes)
+    }
+
+    /// Advances the cursor by `len` bytes.
+    pub fn skip(&mut self, len: usize) -> io::Result<()> {
+        self.read_bytes(len).map(|_| ())
+    }
+
+    /// Reads the next byte.
+    pub fn read_u8(&mut self) -> io::Result<u8> {
+        self.read_array::<1>().map(|[byte]| byte)
+    }
+
+    /// Reads the next byte as an `i8`.
+    pub fn read_i8(&mut self) -> io::Result<i8> {
+        self.read_u8().map(|byte| byte as i8)
+    }
+
+    /// Reads the next LEB128 (protobuf) varint.
+    pub fn read_uvarint(&mut self) -> io::Result<u64> {
+        let (value, len) = uvarint_at(self.bytes, self.position)?;
+        self.position += len;
+        Ok(value)
+    }
+
+    /// Reads the next zigzag-encoded LEB128 varint, as used for protobuf's `sint64`.
+    pub fn read_varint(&mut self) -> io::Result<i64> {
+        self.read_uvarint().map(zigzag_decode)
+    }
+
+    fn read_array<const N: usize>(&mut self) -> io::Result<[u8; N]> {
+        let array = array_at(self.bytes, self.position)?;
+        self.position += N;
+        Ok(array)
+    }
+}
+
+impl<P: AsRef<Path> + Send + Sync> FileReader<P> {
+    /// Returns a `ByteCursor` at the start of the file data.
+    pub fn cursor(&self) -> ByteCursor<'_> {
+        ByteCursor::new(self.as_slice())
+    }
+
+    /// Reads the LEB128 (protobuf) varint at `offset`, returning its value and encoded length.
+    pub fn read_uvarint_at(&self, offset: usize) -> io::Result<(u64, usize)> {
+        uvarint_at(self.as_slice(), offset)
+    }
+
+    /// Reads the zigzag-encoded LEB128 varint at `offset`, returning its value and encoded length.
+    pub fn read_varint_at(&self, offset: usize) -> io::Result<(i64, usize)> {
+        uvarint_at(self.as_slice(), offset).map(|(value, len)| (zigzag_decode(value), len))
+    }
+}
+
+impl<P: AsRef<Path> + Send + Sync> FileWriter<P> {
+    /// Returns a `ByteCursor` at the start of the file data.
+    pub fn cursor(&self) -> ByteCursor<'_> {
+        ByteCursor::new(self.bytes())
+    }
+
+    /// Reads the LEB128 (protobuf) varint at `offset`, returning its value and encoded length.
+    pub fn read_uvarint_at(&self, offset: usize) -> io::Result<(u64, usize)> {
+        uvarint_at(self.bytes(), offset)
+    }
+
+    /// Reads the zigzag-encoded LEB128 varint at `offset`, returning its value and encoded length.
+    pub fn read_varint_at(&self, offset: usize) -> io::Result<(i64, usize)> {
+        uvarint_at(self.bytes(), offset).map(|(value, len)| (zigzag_decode(value), len))
+    }
+
+    /// Writes `value` as a LEB128 (protobuf) varint at `offset`, returning the encoded length.
+    /// The file is not resized; fails with `UnexpectedEof` if the varint would extend past its end.
+    pub fn write_uvarint_at(&mut self, offset: usize, value: u64) -> io::Result<usize> {
+        write_uvarint(self.as_slice_mut(), offset, value)
+    }
+
+    /// Writes `value` as a zigzag-encoded LEB128 varint at `offset`, returning the encoded length.
+    /// The file is not resized; fails with `UnexpectedEof` if the varint would extend past its end.
+    pub fn write_varint_at(&mut self, offset: usize, value: i64) -> io::Result<usize> {
+        write_uvarint(self.as_slice_mut(), offset, zigzag_encode(value))
+    }
+}
+
+/// Generates the fixed-width read and write methods for each numeric type, in both byte orders.
+macro_rules! endian_methods {
+    ($($ty:ident: $from:ident $to:ident $order:literal, $read_at:ident, $write_at:ident, $read:ident;)*) => {
+        impl<P: AsRef<Path> + Send + Sync> FileReader<P> {
+            $(
+                #[doc = concat!("Reads the ", $order, " `", stringify!($ty), "` at `offset`.")]
+                #[doc = "Fails with `UnexpectedEof` if it extends past the end of the file."]
+                pub fn $read_at(&self, offset: usize) -> io::Result<$ty> {
+                    array_at(self.as_slice(), offset).map($ty::$from)
+                }
+            )*
+        }
+
+        impl<P: AsRef<Path> + Send + Sync> FileWriter<P> {
+            $(
+                #[doc = concat!("Reads the ", $order, " `", stringify!($ty), "` at `offset`.")]
+                #[doc = "Fails with `UnexpectedEof` if it extends past the end of the file."]
+                pub fn $read_at(&self, offset: usize) -> io::Result<$ty> {
+                    array_at(self.bytes(), offset).map($ty::$from)
+                }
+
+                #[doc = concat!("Writes `value` as a ", $order, " `", stringify!($ty), "` at `offset`.")]
+                #[doc = "The file is not resized; fails with `UnexpectedEof` if the value would extend past its end."]
+                pub fn $write_at(&mut self, offset: usize, value: $ty) -> io::Result<&Self> {
+                    *array_at_mut(self.as_slice_mut(), offset)? = value.$to();
+                    Ok(self)
+                }
+            )*
+        }
+
+        impl ByteCursor<'_> {
+            $(
+                #[doc = concat!("Reads the next ", $order, " `", stringify!($ty), "`.")]
+                pub fn $read(&mut self) -> io::Result<$ty> {
+                    self.read_array().map($ty::$from)
+                }
+            )*
+        }
+    };
+}
+
+endian_methods! {
+    u16: from_le_bytes to_le_bytes "little-endian", read_u16_le_at, write_u16_le_at, read_u16_le;
+    u16: from_be_bytes to_be_bytes "big-endian", read_u16_be_at, write_u16_be_at, read_u16_be;
+    u32: from_le_bytes to_le_bytes "little-endian", read_u32_le_at, write_u32_le_at, read_u32_le;
+    u32: from_be_bytes to_be_bytes "big-endian", read_u32_be_at, write_u32_be_at, read_u32_be;
+    u64: from_le_bytes to_le_bytes "little-endian", read_u64_le_at, write_u64_le_at, read_u64_le;
+    u64: from_be_bytes to_be_bytes "big-endian", read_u64_be_at, write_u64_be_at, read_u64_be;
+    i16: from_le_bytes to_le_bytes "little-endian", read_i16_le_at, write_i16_le_at, read_i16_le;
+    i16: from_be_bytes to_be_bytes "big-endian", read_i16_be_at, write_i16_be_at, read_i16_be;
+    i32: from_le_bytes to_le_bytes "little-endian", read_i32_le_at, write_i32_le_at, read_i32_le;
+    i32: from_be_bytes to_be_bytes "big-endian", read_i32_be_at, write_i32_be_at, read_i32_be;
+    i64: from_le_bytes to_le_bytes "little-endian", read_i64_le_at, write_i64_le_at, read_i64_le;
+    i64: from_be_bytes to_be_bytes "big-endian", read_i64_be_at, write_i64_be_at, read_i64_be;
+    f32: from_le_bytes to_le_bytes "little-endian", read_f32_le_at, write_f32_le_at, read_f32_le;
+    f32: from_be_bytes to_be_bytes "big-endian", read_f32_be_at, write_f32_be_at, read_f32_be;
+    f64: from_le_bytes to_le_bytes "little-endian", read_f64_le_at, write_f64_le_at, read_f64_le;
+    f64: from_be_bytes to_be_bytes "big-endian", read_f64_be_at, write_f64_be_at, read_f64_be;
+}
@@ -0,0 +1,250 @@
+use crate::{FileReader, FileWriter};
+use std::{io, path::Path};
+
+/// Bytes buffered by a `BitWriter` before they are appended to the file.
+const FLUSH_LEN: usize = 8192;
+
+/// The order in which bits are packed into each byte.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum BitOrder {
+    /// The first bit is the most significant bit of the byte, and values are read most significant bit first,
+    /// as in JPEG, H.264 and most network protocols.
+    #[default]
+    MsbFirst,
+    /// The first bit is the least significant bit of the byte, and values are read least significant bit first,
+    /// as in DEFLATE and GIF's LZW.
+    LsbFirst,
+}
+
+/// Fails with `InvalidInput` if more than 64 bits are requested at once.
+fn check_width(n: u32) -> io::Result<()> {
+    match n <= 64 {
+        true => Ok(()),
+        false => Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("cannot handle {} bits at once, at most 64", n),
+        )),
+    }
+}
+
+/// A reader of arbitrary-width bit fields from a byte slice, such as the data of a `FileReader`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BitReader<'a> {
+    bytes: &'a [u8],
+    /// Position of the next bit to read, in bits from the start of the data.
+    position: usize,
+    order: BitOrder,
+}
+
+impl<'a> BitReader<'a> {
+    /// Creates a reader at the start of the provided bytes.
+    pub fn new(bytes: &'a [u8], order: BitOrder) -> Self {
+        Self {
+            bytes,
+            position: 0,
+            order,
+        }
+    }
+
+    /// Returns the position of the next bit to read, in bits from the start of the data.
+    pub fn bit_position(&self) -> usize {
+        self.position
+    }
+
+    /// Moves the reader to the provided bit position, which may be past the end of the data.
+    pub fn set_bit_position(&mut self, position: usize) {
+        self.position = position;
+    }
+
+    /// Returns the number of bits left to read.
+    pub fn remaining_bits(&self) -> usize {
+        (self.bytes.len() * 8).saturating_sub(self.position)
+    }
+
+    /// Returns the next `n` bits without advancing, as the low bits of the result.
+    /// Fails with `UnexpectedEof` if fewer than `n` bits remain and with `InvalidInput` if `n` is over 64.
+    pub fn peek_bits(&self, n: u32) -> io::Result<u64> {
+        check_width(n)?;
+        if self.remaining_bits() < n as usize {
+            return Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                format!(
+                    "{} bits at bit position {} extend past the end of the data",
+                    n, self.position
+                ),
+            ));
+        }
+        let mut value = 0u64;
+        let mut position = self.position;
+        let mut shift = 0;
+        let mut left = n;
+        while left > 0 {
+            let byte = self.bytes[position / 8];
+            let offset = (position % 8) as u32;
+            let take = left.min(8 - offset);
+            let mask = (1u64 << take) - 1;
+            match self.order {
+                BitOrder::MsbFirst => {
+                    let bits = u64::from(byte >> (8 - offset - take)) & mask;
+                    value = (value << take) | bits;
+                }
+                BitOrder::LsbFirst => {
+                    let bits = u64::from(byte >> offset) & mask;
+                    value |= bits << shift;
+                    shift += take;
+                }
+            }
+            position += take as usize;
+            left -= take;
+        }
+        Ok(value)
+    }
+
+    /// Reads the next `n` bits, as the low bits of the result.
+    /// On error the position is left unchanged.
+    pub fn read_bits(&mut self, n: u32) -> io::Result<u64> {
+        let value = self.peek_bits(n)?;
+        self.position += n as usize;
+        Ok(value)
+    }
+
+    /// Reads the next bit.
+    pub fn read_bit(&mut self) -> io::Result<bool> {
+        self.read_bits(1).map(|bit| bit == 1)
+    }
+
+    /// Advances the reader by `n` bits.
+    pub fn skip_bits(&mut self, n: usize) -> io::Result<()> {
+        if self.remaining_bits() < n {
+            return Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                format!("cannot skip {} bits, {} remain", n, self.remaining_bits()),
+            ));
+        }
+        self.position += n;
+        Ok(())
+    }
+
+    /// Advances the reader to the start of the next byte, unless it is already at the start of one.
+    pub fn align_to_byte(&mut self) {
+        self.position = self.position.next_multiple_of(8);
+    }
+}
+
+/// A writer that appends packed bit fields to the end of a `FileWriter`, growing the file as needed.
+///
+/// Complete bytes are buffered and appended in batches. `finish` pads the last partial byte with
+/// zero bits and appends everything still buffered; dropping the writer does the same but ignores errors.
+pub struct BitWriter<'w, P: AsRef<Path> + Send + Sync> {
+    writer: &'w mut FileWriter<P>,
+    order: BitOrder,
+    buffer: Vec<u8>,
+    /// The partial byte being filled and the number of bits in it.
+    current: u8,
+    filled: u32,
+    /// Number of bits written so far, including those still buffered.
+    position: usize,
+}
+
+impl<'w, P: AsRef<Path> + Send + Sync> BitWriter<'w, P> {
+    /// Creates a writer that appends to the end of the file.
+    pub fn new(writer: &'w mut FileWriter<P>, order: BitOrder) -> Self {
+        Self {
+            writer,
+            order,
+            buffer: Vec::new(),
+            current: 0,
+            filled: 0,
+            position: 0,
+        }
+    }
+
+    /// Returns the number of bits written so far.
+    pub fn bit_position(&self) -> usize {
+        self.position
+    }
+
+    /// Writes the low `n` bits of `value`. Fails with `InvalidInput` if `n` is over 64.
+    pub fn write_bits(&mut self, value: u64, n: u32) -> io::Result<()> {
+        check_width(n)?;
+        let mut left = n;
+        let mut value = value;
+        while left > 0 {
+            let space = 8 - self.filled;
+            let take = left.min(space);
+            let mask = (1u64 << take) - 1;
+            match self.order {
+                BitOrder::MsbFirst => {
+                    let bits = (value >> (left - take)) & mask;
+                    self.current |= (bits as u8) << (space - take);
+                }
+                BitOrder::LsbFirst => {
+                    let bits = value & mask;
+                    value >>= take;
+                    self.current |= (bits as u8) << self.filled;
+                }
+            }
+            self.filled += take;
+            left -= take;
+            if self.filled == 8 {
+                self.buffer.push(self.current);
+                self.current = 0;
+                self.filled = 0;
+            }
+        }
+        self.position += n as usize;
+        if self.buffer.len() >= FLUSH_LEN {
+            self.flush()?;
+        }
+        Ok(())
+    }
+
+    /// Writes a single bit.
+    pub fn write_bit(&mut self, bit: bool) -> io::Result<()> {
+        self.write_bits(u64::from(bit), 1)
+    }
+
+    /// Pads the current byte with zero bits, unless it is already complete.
+    pub fn align_to_byte(&mut self) -> io::Result<()> {
+        match self.filled {
+            0 => Ok(()),
+            filled => self.write_bits(0, 8 - filled),
+        }
+    }
+
+    /// Appends all complete bytes written so far to the file.
+    /// A partial byte stays buffered until it is completed or the writer is finished.
+    pub fn flush(&mut self) -> io::Result<()> {
+        if !self.buffer.is_empty() {
+            self.writer.append(&self.buffer)?;
+            self.buffer.clear();
+        }
+        Ok(())
+    }
+
+    /// Pads the last partial byte with zero bits and appends everything written to the file.
+    pub fn finish(mut self) -> io::Result<()> {
+        self.align_to_byte()?;
+        self.flush()
+    }
+}
+
+impl<P: AsRef<Path> + Send + Sync> Drop for BitWriter<'_, P> {
+    fn drop(&mut self) {
+        let _ = self.align_to_byte().and_then(|_| self.flush());
+    }
+}
+
+impl<P: AsRef<Path> + Send + Sync> FileReader<P> {
+    /// Returns a `BitReader` at the start of the file data.
+    pub fn bit_reader(&self, order: BitOrder) -> BitReader<'_> {
+        BitReader::new(self.as_slice(), order)
+    }
+}
+
+impl<P: AsRef<Path> + Send + Sync> FileWriter<P> {
+    /// Returns a `BitWriter` that appends packed bits to the end of the file.
+    pub fn bit_writer(&mut self, order: BitOrder) -> BitWriter<'_, P> {
+        BitWriter::new(self, order)
+    }
+}
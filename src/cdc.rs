@@ -0,0 +1,87 @@
+use crate::FileReader;
+use fastcdc::v2020::{
+    FastCDC, AVERAGE_MAX, AVERAGE_MIN, MAXIMUM_MAX, MAXIMUM_MIN, MINIMUM_MAX, MINIMUM_MIN,
+};
+use std::{
+    io,
+    ops::{Range, RangeInclusive},
+    path::Path,
+};
+
+#[cfg(feature = "hash")]
+use digest::{Digest, Output};
+
+/// Converts a chunk size to `u32`, failing with `InvalidInput` if it is outside `bounds`.
+fn chunk_size(name: &str, size: usize, bounds: RangeInclusive<u32>) -> io::Result<u32> {
+    u32::try_from(size)
+        .ok()
+        .filter(|size| bounds.contains(size))
+        .ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("{} chunk size {} is outside {:?}", name, size, bounds),
+            )
+        })
+}
+
+/// An iterator over content-defined chunks of some data, yielding each chunk's byte range and the
+/// gear hash that ended it. Returned by `FileReader::cdc_chunks`.
+pub struct CdcChunks<'a> {
+    chunker: FastCDC<'a>,
+}
+
+impl<'a> CdcChunks<'a> {
+    /// Creates an iterator over the FastCDC chunks of the provided bytes.
+    /// Chunks are at least `min` and at most `max` bytes, except that the last chunk may be shorter
+    /// than `min`, and average around `avg` bytes.
+    /// Fails with `InvalidInput` if `min` is not within 64 bytes to 1 MiB, `avg` within 256 bytes to
+    /// 4 MiB, or `max` within 1 KiB to 16 MiB, or if they are out of order.
+    pub fn new(bytes: &'a [u8], min: usize, avg: usize, max: usize) -> io::Result<Self> {
+        let min = chunk_size("minimum", min, MINIMUM_MIN..=MINIMUM_MAX)?;
+        let avg = chunk_size("average", avg, AVERAGE_MIN..=AVERAGE_MAX)?;
+        let max = chunk_size("maximum", max, MAXIMUM_MIN..=MAXIMUM_MAX)?;
+        if min > avg || avg > max {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "chunk sizes must satisfy min <= avg <= max",
+            ));
+        }
+        Ok(Self {
+            chunker: FastCDC::new(bytes, min, avg, max),
+        })
+    }
+}
+
+impl Iterator for CdcChunks<'_> {
+    type Item = (Range<usize>, u64);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let chunk = self.chunker.next()?;
+        Some((chunk.offset..chunk.offset + chunk.length, chunk.hash))
+    }
+}
+
+impl<P: AsRef<Path> + Send + Sync> FileReader<P> {
+    /// Splits the file data into content-defined chunks with FastCDC, yielding each chunk's byte range
+    /// and the gear hash that ended it. Boundaries depend only on the data, so they are the same across
+    /// runs and shift with insertions rather than moving every later chunk, which suits deduplication.
+    /// Chunks are borrowed from the mmap via their ranges, without copying. See `CdcChunks::new` for the
+    /// allowed sizes.
+    pub fn cdc_chunks(&self, min: usize, avg: usize, max: usize) -> io::Result<CdcChunks<'_>> {
+        CdcChunks::new(self.as_slice(), min, avg, max)
+    }
+
+    #[cfg(feature = "hash")]
+    /// Splits the file data into content-defined chunks as `cdc_chunks` does, yielding each chunk's
+    /// byte range and its hash with the given hash function, such as a content address for deduplication.
+    pub fn cdc_chunks_with<H: Digest>(
+        &self,
+        min: usize,
+        avg: usize,
+        max: usize,
+    ) -> io::Result<impl Iterator<Item = (Range<usize>, Output<H>)> + '_> {
+        let bytes = self.as_slice();
+        Ok(CdcChunks::new(bytes, min, avg, max)?
+            .map(move |(range, _)| (range.clone(), H::digest(&bytes[range]))))
+    }
+}
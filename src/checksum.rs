@@ -0,0 +1,193 @@
+use crate::FileReader;
+use std::{fmt, io, mem::size_of, ops::Range, path::Path};
+
+/// A non-cryptographic checksum computed incrementally over some data, for integrity checks where
+/// `digest::Digest` implementations are unavailable or unnecessarily slow.
+pub trait Checksum: Default {
+    /// The checksum value.
+    type Output: Copy + Eq + fmt::Debug + fmt::LowerHex;
+
+    /// Feeds `bytes` to the checksum.
+    fn update(&mut self, bytes: &[u8]);
+
+    /// Returns the checksum of all the data fed so far.
+    fn finalize(self) -> Self::Output;
+
+    /// Computes the checksum of `bytes` in one call.
+    fn checksum(bytes: &[u8]) -> Self::Output {
+        let mut checksum = Self::default();
+        checksum.update(bytes);
+        checksum.finalize()
+    }
+}
+
+static CRC32: crc::Crc<u32, crc::Table<16>> =
+    crc::Crc::<u32, crc::Table<16>>::new(&crc::CRC_32_ISO_HDLC);
+static CRC32C: crc::Crc<u32, crc::Table<16>> =
+    crc::Crc::<u32, crc::Table<16>>::new(&crc::CRC_32_ISCSI);
+
+/// CRC-32 (ISO-HDLC), as used by zlib, gzip, PNG and ZIP.
+pub struct Crc32(crc::Digest<'static, u32, crc::Table<16>>);
+
+impl Default for Crc32 {
+    fn default() -> Self {
+        Self(CRC32.digest())
+    }
+}
+
+impl Checksum for Crc32 {
+    type Output = u32;
+
+    fn update(&mut self, bytes: &[u8]) {
+        self.0.update(bytes);
+    }
+
+    fn finalize(self) -> u32 {
+        self.0.finalize()
+    }
+}
+
+/// CRC-32C (Castagnoli), as used by iSCSI, ext4, Btrfs and many storage formats.
+pub struct Crc32c(crc::Digest<'static, u32, crc::Table<16>>);
+
+impl Default for Crc32c {
+    fn default() -> Self {
+        Self(CRC32C.digest())
+    }
+}
+
+impl Checksum for Crc32c {
+    type Output = u32;
+
+    fn update(&mut self, bytes: &[u8]) {
+        self.0.update(bytes);
+    }
+
+    fn finalize(self) -> u32 {
+        self.0.finalize()
+    }
+}
+
+/// Adler-32, as used by zlib streams.
+#[derive(Default)]
+pub struct Adler32(adler2::Adler32);
+
+impl Checksum for Adler32 {
+    type Output = u32;
+
+    fn update(&mut self, bytes: &[u8]) {
+        self.0.write_slice(bytes);
+    }
+
+    fn finalize(self) -> u32 {
+        self.0.checksum()
+    }
+}
+
+/// xxHash64 with a seed of zero.
+pub struct XxHash64(xxhash_rust::xxh64::Xxh64);
+
+impl Default for XxHash64 {
+    fn default() -> Self {
+        Self(xxhash_rust::xxh64::Xxh64::new(0))
+    }
+}
+
+impl Checksum for XxHash64 {
+    type Output = u64;
+
+    fn update(&mut self, bytes: &[u8]) {
+        self.0.update(bytes);
+    }
+
+    fn finalize(self) -> u64 {
+        self.0.digest()
+    }
+}
+
+/// XXH3 with 64-bit output and the default secret.
+#[derive(Default)]
+pub struct Xxh3(xxhash_rust::xxh3::Xxh3);
+
+impl Checksum for Xxh3 {
+    type Output = u64;
+
+    fn update(&mut self, bytes: &[u8]) {
+        self.0.update(bytes);
+    }
+
+    fn finalize(self) -> u64 {
+        self.0.digest()
+    }
+}
+
+/// Compares two files by their checksums computed with the given checksum algorithm.
+/// It takes two file paths `file_path1` and `file_path2`, and returns true if the files are identical (based on their checksums), false otherwise.
+pub fn compare_files_checksum<C: Checksum>(
+    file_path1: impl AsRef<Path> + Send + Sync,
+    file_path2: impl AsRef<Path> + Send + Sync,
+) -> bool {
+    if let Ok(file1_reader) = FileReader::open(&file_path1) {
+        if let Ok(file2_reader) = FileReader::open(&file_path2) {
+            return file1_reader.checksum::<C>() == file2_reader.checksum::<C>();
+        }
+    }
+    false
+}
+
+impl<P: AsRef<Path> + Send + Sync> FileReader<P> {
+    /// Computes the checksum of the file data using the given checksum algorithm.
+    pub fn checksum<C: Checksum>(&self) -> C::Output {
+        C::checksum(self.as_slice())
+    }
+
+    /// Computes the checksum of a byte range of the file data using the given checksum algorithm.
+    /// Fails with `UnexpectedEof` if the range extends past the end of the file.
+    pub fn checksum_range<C: Checksum>(&self, range: Range<usize>) -> io::Result<C::Output> {
+        let len = self.as_slice().len();
+        let bytes = self.as_slice().get(range.clone()).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                format!("range {:?} is out of bounds for {} bytes", range, len),
+            )
+        })?;
+        Ok(C::checksum(bytes))
+    }
+
+    /// Feeds the file data to an existing checksum, so several files can be checked together.
+    /// Returns the FileReader for chaining.
+    pub fn update_checksum<C: Checksum>(&self, checksum: &mut C) -> &Self {
+        checksum.update(self.as_slice());
+        self
+    }
+
+    /// Computes the checksum of the file data using the given checksum algorithm and returns it as a
+    /// zero-padded hex string.
+    pub fn checksum_to_string<C: Checksum>(&self) -> String {
+        format!(
+            "{:0width$x}",
+            self.checksum::<C>(),
+            width = size_of::<C::Output>() * 2
+        )
+    }
+
+    /// Compares the checksum of the FileReader's file to a given checksum.
+    /// It takes a checksum `checksum`, and returns true if the checksum of the file is identical to the given checksum, false otherwise.
+    pub fn compare_checksum<C: Checksum>(&self, checksum: C::Output) -> bool {
+        self.checksum::<C>() == checksum
+    }
+
+    /// Compares the FileReader's file to another file by their checksums computed with the given
+    /// checksum algorithm.
+    /// It takes a file path `file_path`, and returns true if the files are identical (based on their checksums), false otherwise.
+    pub fn compare_to_file_at_path_checksum<C: Checksum>(
+        &self,
+        file_path: impl AsRef<Path> + Send + Sync,
+    ) -> bool {
+        if let Ok(file_reader) = FileReader::open(&file_path) {
+            self.checksum::<C>() == file_reader.checksum::<C>()
+        } else {
+            false
+        }
+    }
+}
@@ -0,0 +1,306 @@
+use crate::{FileReader, FileWriter};
+use std::{io, ops::Range, path::Path};
+
+/// Byte order of a chunk's length field.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Endian {
+    Little,
+    Big,
+}
+
+/// The header layout of a tagged, length-prefixed chunk format.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ChunkLayout {
+    /// Length of the chunk tag in bytes.
+    pub tag_len: usize,
+    /// Width of the length field in bytes, from 1 to 8. The length counts the chunk data only.
+    pub length_len: usize,
+    /// Byte order of the length field.
+    pub length_endian: Endian,
+    /// Whether the length field comes before the tag, as in PNG, rather than after it.
+    pub length_first: bool,
+    /// Chunk data is padded to a multiple of this many bytes, not counted in the length. 1 for no padding.
+    pub padding: usize,
+    /// Length of the CRC trailing each chunk, 0 for none or 4 for a CRC-32 of the tag and data.
+    pub crc_len: usize,
+    /// Offset of the first chunk, past any file signature or container header.
+    pub start: usize,
+    /// Offset of a container length field covering everything after it, as in RIFF and IFF files.
+    /// It has the same width and byte order as chunk lengths, and `ChunkWriter` keeps it up to date.
+    pub container_length_at: Option<usize>,
+}
+
+impl ChunkLayout {
+    /// The layout of RIFF files such as WAV and AVI, starting after the `RIFF` header and form type.
+    pub fn riff() -> Self {
+        Self {
+            tag_len: 4,
+            length_len: 4,
+            length_endian: Endian::Little,
+            length_first: false,
+            padding: 2,
+            crc_len: 0,
+            start: 12,
+            container_length_at: Some(4),
+        }
+    }
+
+    /// The layout of PNG files, starting after the 8-byte signature.
+    pub fn png() -> Self {
+        Self {
+            tag_len: 4,
+            length_len: 4,
+            length_endian: Endian::Big,
+            length_first: true,
+            padding: 1,
+            crc_len: 4,
+            start: 8,
+            container_length_at: None,
+        }
+    }
+
+    /// The layout of IFF files such as AIFF and ILBM, starting after the `FORM` header and form type.
+    pub fn iff() -> Self {
+        Self {
+            length_endian: Endian::Big,
+            ..Self::riff()
+        }
+    }
+
+    /// Returns the combined length of the tag and length fields.
+    fn header_len(&self) -> usize {
+        self.tag_len + self.length_len
+    }
+
+    /// Returns the offsets of the tag and length fields within the chunk header.
+    fn field_offsets(&self) -> (usize, usize) {
+        match self.length_first {
+            true => (self.length_len, 0),
+            false => (0, self.tag_len),
+        }
+    }
+
+    /// Returns the number of padding bytes following `len` bytes of chunk data.
+    fn padding_len(&self, len: usize) -> usize {
+        len.next_multiple_of(self.padding.max(1)) - len
+    }
+
+    fn decode_length(&self, bytes: &[u8]) -> u64 {
+        let fold = |value: u64, byte: &u8| (value << 8) | u64::from(*byte);
+        match self.length_endian {
+            Endian::Little => bytes.iter().rev().fold(0, fold),
+            Endian::Big => bytes.iter().fold(0, fold),
+        }
+    }
+
+    /// Encodes `len` in the length field's width and byte order, failing if it does not fit.
+    fn encode_length(&self, len: usize) -> io::Result<Vec<u8>> {
+        let len = len as u64;
+        if self.length_len < 8 && len >> (self.length_len * 8) != 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "length {} does not fit in a {}-byte chunk length",
+                    len, self.length_len
+                ),
+            ));
+        }
+        let bytes = len.to_le_bytes()[..self.length_len].to_vec();
+        Ok(match self.length_endian {
+            Endian::Little => bytes,
+            Endian::Big => bytes.into_iter().rev().collect(),
+        })
+    }
+
+    /// Fails with `InvalidInput` if the layout cannot be read or written.
+    fn validate(&self) -> io::Result<()> {
+        if !(1..=8).contains(&self.length_len) || !matches!(self.crc_len, 0 | 4) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "chunk lengths must be 1 to 8 bytes wide and CRCs 0 or 4 bytes long",
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Computes the CRC-32 (ISO-HDLC, as used by PNG and zlib) of the provided byte slices.
+fn crc32(parts: &[&[u8]]) -> u32 {
+    let mut crc = !0u32;
+    for byte in parts.iter().flat_map(|part| part.iter()) {
+        crc ^= u32::from(*byte);
+        for _ in 0..8 {
+            crc = (crc >> 1) ^ (0xedb8_8320 & (crc & 1).wrapping_neg());
+        }
+    }
+    !crc
+}
+
+/// A zero-copy iterator over the chunks of a tagged, length-prefixed format, yielding each chunk's
+/// tag and the range of its data. A truncated chunk yields an `UnexpectedEof` error and ends iteration.
+#[derive(Clone, Debug)]
+pub struct ChunkIter<'a> {
+    bytes: &'a [u8],
+    layout: ChunkLayout,
+    /// Offset of the next chunk, or `None` once iteration has ended.
+    offset: Option<usize>,
+}
+
+impl<'a> ChunkIter<'a> {
+    /// Creates an iterator over the chunks in the provided bytes, starting at `layout.start`.
+    pub fn new(bytes: &'a [u8], layout: ChunkLayout) -> Self {
+        Self {
+            bytes,
+            layout,
+            offset: Some(layout.start),
+        }
+    }
+
+    /// Returns the offset of the next chunk header.
+    pub fn offset(&self) -> Option<usize> {
+        self.offset
+    }
+
+    fn read_chunk(&self, offset: usize) -> io::Result<(&'a [u8], Range<usize>, usize)> {
+        self.layout.validate()?;
+        let truncated = || {
+            io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                format!("chunk at offset {} is truncated", offset),
+            )
+        };
+        let header = self
+            .bytes
+            .get(offset..offset + self.layout.header_len())
+            .ok_or_else(truncated)?;
+        let (tag_offset, length_offset) = self.layout.field_offsets();
+        let tag = &header[tag_offset..tag_offset + self.layout.tag_len];
+        let len = self
+            .layout
+            .decode_length(&header[length_offset..length_offset + self.layout.length_len]);
+        let data_start = offset + header.len();
+        let data = usize::try_from(len)
+            .ok()
+            .and_then(|len| Some(data_start..data_start.checked_add(len)?))
+            .filter(|data| data.end <= self.bytes.len())
+            .ok_or_else(truncated)?;
+        let next = data.end + self.layout.padding_len(data.len()) + self.layout.crc_len;
+        if data.end + self.layout.crc_len > self.bytes.len() {
+            return Err(truncated());
+        }
+        Ok((tag, data, next.min(self.bytes.len())))
+    }
+}
+
+impl<'a> Iterator for ChunkIter<'a> {
+    type Item = io::Result<(&'a [u8], Range<usize>)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let offset = self.offset.filter(|offset| *offset < self.bytes.len())?;
+        match self.read_chunk(offset) {
+            Ok((tag, data, next)) => {
+                self.offset = Some(next);
+                Some(Ok((tag, data)))
+            }
+            Err(error) => {
+                self.offset = None;
+                Some(Err(error))
+            }
+        }
+    }
+}
+
+/// A writer that appends chunks to the end of a `FileWriter` in a chunk layout, adding padding and
+/// CRCs and keeping any container length field up to date. The file signature or container header
+/// must already be written.
+pub struct ChunkWriter<'w, P: AsRef<Path> + Send + Sync> {
+    writer: &'w mut FileWriter<P>,
+    layout: ChunkLayout,
+}
+
+impl<'w, P: AsRef<Path> + Send + Sync> ChunkWriter<'w, P> {
+    /// Creates a writer that appends chunks in the provided layout.
+    pub fn new(writer: &'w mut FileWriter<P>, layout: ChunkLayout) -> Self {
+        Self { writer, layout }
+    }
+
+    /// Appends a chunk with the provided tag and data, returning the range of its data in the file.
+    /// Fails with `InvalidInput` if the tag has the wrong length or the data is too long for the length field.
+    pub fn write_chunk<T: AsRef<[u8]>, B: AsRef<[u8]>>(
+        &mut self,
+        tag: T,
+        data: B,
+    ) -> io::Result<Range<usize>> {
+        let (tag, data) = (tag.as_ref(), data.as_ref());
+        self.layout.validate()?;
+        if tag.len() != self.layout.tag_len {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "chunk tags are {} bytes, not {}",
+                    self.layout.tag_len,
+                    tag.len()
+                ),
+            ));
+        }
+        let length = self.layout.encode_length(data.len())?;
+        let mut chunk = Vec::with_capacity(
+            self.layout.header_len() + data.len() + self.layout.padding + self.layout.crc_len,
+        );
+        match self.layout.length_first {
+            true => chunk.extend_from_slice(&length),
+            false => chunk.extend_from_slice(tag),
+        }
+        match self.layout.length_first {
+            true => chunk.extend_from_slice(tag),
+            false => chunk.extend_from_slice(&length),
+        }
+        chunk.extend_from_slice(data);
+        chunk.resize(chunk.len() + self.layout.padding_len(data.len()), 0);
+        if self.layout.crc_len == 4 {
+            chunk.extend_from_slice(&crc32(&[tag, data]).to_be_bytes());
+        }
+        let data_start = self.writer.len() + self.layout.header_len();
+        self.writer.append(chunk)?;
+        self.update_container_length()?;
+        Ok(data_start..data_start + data.len())
+    }
+
+    /// Sets the container length field to the number of bytes after it, if the layout has one.
+    fn update_container_length(&mut self) -> io::Result<()> {
+        let Some(at) = self.layout.container_length_at else {
+            return Ok(());
+        };
+        let end = at + self.layout.length_len;
+        let length = self
+            .layout
+            .encode_length(self.writer.len().saturating_sub(end))?;
+        if end > self.writer.len() {
+            return Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                "the container header has not been written",
+            ));
+        }
+        self.writer.write_to_offset(length, at);
+        Ok(())
+    }
+}
+
+impl<P: AsRef<Path> + Send + Sync> FileReader<P> {
+    /// Returns an iterator over the file's chunks in the provided layout.
+    pub fn chunks(&self, layout: ChunkLayout) -> ChunkIter<'_> {
+        ChunkIter::new(self.as_slice(), layout)
+    }
+}
+
+impl<P: AsRef<Path> + Send + Sync> FileWriter<P> {
+    /// Returns an iterator over the file's chunks in the provided layout.
+    pub fn chunks(&self, layout: ChunkLayout) -> ChunkIter<'_> {
+        ChunkIter::new(self.bytes(), layout)
+    }
+
+    /// Returns a `ChunkWriter` that appends chunks in the provided layout to the end of the file.
+    pub fn chunk_writer(&mut self, layout: ChunkLayout) -> ChunkWriter<'_, P> {
+        ChunkWriter::new(self, layout)
+    }
+}
@@ -0,0 +1,333 @@
+use crate::FileReader;
+use std::{borrow::Cow, io, ops::Range, path::Path};
+
+/// Options controlling how a `FileReader` is parsed as CSV, TSV or similar delimited data.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CsvOptions {
+    /// The byte separating fields, `,` by default.
+    pub delimiter: u8,
+    /// The byte used to quote fields, `"` by default. Inside a quoted field, two quotes stand for one.
+    pub quote: u8,
+    /// Whether the first record is a header naming the columns, true by default.
+    pub has_header: bool,
+}
+
+impl Default for CsvOptions {
+    fn default() -> Self {
+        Self {
+            delimiter: b',',
+            quote: b'"',
+            has_header: true,
+        }
+    }
+}
+
+impl CsvOptions {
+    /// Returns options for tab-separated values with a header.
+    pub fn tsv() -> Self {
+        Self {
+            delimiter: b'\t',
+            ..Self::default()
+        }
+    }
+}
+
+/// Returns the offset just past the record starting at `start`, including its line ending.
+/// Line endings inside quoted fields do not end the record.
+fn record_end(bytes: &[u8], start: usize, options: &CsvOptions) -> usize {
+    let mut quoted = false;
+    let mut i = start;
+    while i < bytes.len() {
+        match bytes[i] {
+            byte if byte == options.quote => quoted = !quoted,
+            b'\n' if !quoted => return i + 1,
+            _ => {}
+        }
+        i += 1;
+    }
+    bytes.len()
+}
+
+/// Strips the line ending from the end of a record.
+fn trim_line_ending(record: &[u8]) -> &[u8] {
+    let record = record.strip_suffix(b"\n").unwrap_or(record);
+    record.strip_suffix(b"\r").unwrap_or(record)
+}
+
+/// A record of delimited data, borrowed from the mapped file.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CsvRecord<'a> {
+    bytes: &'a [u8],
+    offset: usize,
+    options: CsvOptions,
+}
+
+impl<'a> CsvRecord<'a> {
+    /// Returns the raw bytes of the record, excluding its line ending.
+    pub fn as_bytes(&self) -> &'a [u8] {
+        self.bytes
+    }
+
+    /// Returns the byte offset of the record in the file.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Returns an iterator over the record's fields.
+    /// Quoted fields are returned without their surrounding quotes but with any doubled quotes left
+    /// in place; pass them to `CsvRecord::unescape` to collapse those.
+    pub fn fields(&self) -> CsvFields<'a> {
+        CsvFields {
+            bytes: self.bytes,
+            position: Some(0),
+            options: self.options,
+        }
+    }
+
+    /// Returns the nth field of the record.
+    pub fn field(&self, n: usize) -> Option<&'a [u8]> {
+        self.fields().nth(n)
+    }
+
+    /// Returns the number of fields in the record.
+    pub fn len(&self) -> usize {
+        self.fields().count()
+    }
+
+    /// Returns true if the record has no bytes. An empty record still has a single empty field.
+    pub fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    /// Collapses doubled quotes in a field returned by `fields`, borrowing when there are none.
+    pub fn unescape(&self, field: &'a [u8]) -> Cow<'a, [u8]> {
+        let quote = self.options.quote;
+        if !field.windows(2).any(|pair| pair == [quote, quote]) {
+            return Cow::Borrowed(field);
+        }
+        let mut unescaped = Vec::with_capacity(field.len());
+        let mut bytes = field.iter().peekable();
+        while let Some(&byte) = bytes.next() {
+            unescaped.push(byte);
+            if byte == quote && bytes.peek() == Some(&&quote) {
+                bytes.next();
+            }
+        }
+        Cow::Owned(unescaped)
+    }
+}
+
+/// An iterator over the fields of a `CsvRecord`.
+#[derive(Clone, Debug)]
+pub struct CsvFields<'a> {
+    bytes: &'a [u8],
+    /// Offset of the next field, or `None` once every field has been returned.
+    position: Option<usize>,
+    options: CsvOptions,
+}
+
+impl<'a> Iterator for CsvFields<'a> {
+    type Item = &'a [u8];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let start = self.position?;
+        let CsvOptions {
+            delimiter, quote, ..
+        } = self.options;
+        if self.bytes.get(start) == Some(&quote) {
+            let mut i = start + 1;
+            while i < self.bytes.len() {
+                if self.bytes[i] == quote {
+                    if self.bytes.get(i + 1) == Some(&quote) {
+                        i += 2;
+                        continue;
+                    }
+                    break;
+                }
+                i += 1;
+            }
+            let field = &self.bytes[start + 1..i.min(self.bytes.len())];
+            let delimiter_offset = self.bytes[i.min(self.bytes.len())..]
+                .iter()
+                .position(|byte| *byte == delimiter);
+            self.position = delimiter_offset.map(|offset| i + offset + 1);
+            return Some(field);
+        }
+        match self.bytes[start..]
+            .iter()
+            .position(|byte| *byte == delimiter)
+        {
+            Some(len) => {
+                self.position = Some(start + len + 1);
+                Some(&self.bytes[start..start + len])
+            }
+            None => {
+                self.position = None;
+                Some(&self.bytes[start..])
+            }
+        }
+    }
+}
+
+/// An iterator over the records of a `CsvReader`. Blank lines are skipped rather than read as records.
+#[derive(Clone, Debug)]
+pub struct CsvRecords<'a> {
+    bytes: &'a [u8],
+    offset: usize,
+    options: CsvOptions,
+}
+
+impl<'a> Iterator for CsvRecords<'a> {
+    type Item = CsvRecord<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.offset < self.bytes.len() {
+            let start = self.offset;
+            self.offset = record_end(self.bytes, start, &self.options);
+            let bytes = trim_line_ending(&self.bytes[start..self.offset]);
+            if !bytes.is_empty() {
+                return Some(CsvRecord {
+                    bytes,
+                    offset: start,
+                    options: self.options,
+                });
+            }
+        }
+        None
+    }
+}
+
+/// A zero-copy reader of delimited records (RFC 4180 CSV, TSV and similar) over the contents of a `FileReader`.
+/// Records and fields are slices borrowed from the mmap. Blank lines between records are skipped.
+#[derive(Clone, Debug)]
+pub struct CsvReader<'a> {
+    bytes: &'a [u8],
+    options: CsvOptions,
+    header: Option<CsvRecord<'a>>,
+    /// Offset of the first record after the header.
+    data_start: usize,
+    /// Record start offsets, built on first use by `CsvReader::build_index`.
+    index: Option<Vec<usize>>,
+}
+
+impl<'a> CsvReader<'a> {
+    /// Creates a reader over the provided bytes.
+    pub fn new(bytes: &'a [u8], options: CsvOptions) -> Self {
+        let mut reader = Self {
+            bytes,
+            options,
+            header: None,
+            data_start: 0,
+            index: None,
+        };
+        if options.has_header {
+            let mut records = reader.records();
+            reader.header = records.next();
+            reader.data_start = records.offset;
+        }
+        reader
+    }
+
+    /// Returns the header record, if the options declare one.
+    pub fn header(&self) -> Option<CsvRecord<'a>> {
+        self.header
+    }
+
+    /// Returns the position of the named column in the header.
+    pub fn column_index<B: AsRef<[u8]>>(&self, name: B) -> Option<usize> {
+        self.header?
+            .fields()
+            .position(|field| field == name.as_ref())
+    }
+
+    /// Returns an iterator over the data records, excluding the header.
+    pub fn records(&self) -> CsvRecords<'a> {
+        CsvRecords {
+            bytes: self.bytes,
+            offset: self.data_start,
+            options: self.options,
+        }
+    }
+
+    /// Returns an iterator over the named column's field in every data record.
+    /// Records too short to have the column yield an empty field.
+    /// Fails with `NotFound` if there is no header or no column of that name.
+    pub fn column<B: AsRef<[u8]>>(
+        &self,
+        name: B,
+    ) -> io::Result<impl Iterator<Item = &'a [u8]> + 'a> {
+        let name = name.as_ref();
+        let n = self.column_index(name).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("no CSV column named {:?}", String::from_utf8_lossy(name)),
+            )
+        })?;
+        Ok(self
+            .records()
+            .map(move |record| record.field(n).unwrap_or_default()))
+    }
+
+    /// Builds the index of record start offsets, scanning the data once.
+    /// After this, `record` and `record_count` no longer reparse the data.
+    pub fn build_index(&mut self) -> &[usize] {
+        let records = self.records();
+        self.index
+            .get_or_insert_with(|| records.map(|record| record.offset).collect())
+    }
+
+    /// Returns the offsets of every data record, if the index has been built.
+    pub fn index(&self) -> Option<&[usize]> {
+        self.index.as_deref()
+    }
+
+    /// Returns the number of data records, using the index if it has been built.
+    pub fn record_count(&self) -> usize {
+        match &self.index {
+            Some(index) => index.len(),
+            None => self.records().count(),
+        }
+    }
+
+    /// Returns the nth data record (zero-based, excluding the header).
+    /// Jumps straight to the record if the index has been built, and scans from the start otherwise.
+    pub fn record(&self, n: usize) -> Option<CsvRecord<'a>> {
+        match &self.index {
+            Some(index) => CsvRecords {
+                bytes: self.bytes,
+                offset: *index.get(n)?,
+                options: self.options,
+            }
+            .next(),
+            None => self.records().nth(n),
+        }
+    }
+
+    /// Returns the data record starting at the provided byte offset.
+    pub fn record_at(&self, offset: usize) -> Option<CsvRecord<'a>> {
+        CsvRecords {
+            bytes: self.bytes,
+            offset,
+            options: self.options,
+        }
+        .next()
+    }
+
+    /// Returns the byte range of the nth data record, excluding its line ending.
+    pub fn record_range(&self, n: usize) -> Option<Range<usize>> {
+        let record = self.record(n)?;
+        Some(record.offset..record.offset + record.bytes.len())
+    }
+}
+
+impl<P: AsRef<Path> + Send + Sync> FileReader<P> {
+    /// Returns a zero-copy reader of the file's comma-separated records, treating the first record as a header.
+    pub fn csv(&self) -> CsvReader<'_> {
+        CsvReader::new(self.as_slice(), CsvOptions::default())
+    }
+
+    /// Returns a zero-copy reader of the file's delimited records with the provided options.
+    pub fn csv_with(&self, options: CsvOptions) -> CsvReader<'_> {
+        CsvReader::new(self.as_slice(), options)
+    }
+}
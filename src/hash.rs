@@ -0,0 +1,303 @@
+#[cfg(feature = "hash_encoding")]
+use crate::binary::{encode_uvarint, uvarint_at};
+#[cfg(feature = "hash_encoding")]
+use base64::{
+    alphabet,
+    engine::{DecodePaddingMode, GeneralPurpose, GeneralPurposeConfig},
+    Engine,
+};
+use digest::{Digest, Output};
+#[cfg(feature = "hash_encoding")]
+use std::io;
+
+/// Bytes fed to every hasher in turn by `MultiDigest::update_chunked`, small enough to stay in cache.
+const CHUNK_LEN: usize = 64 * 1024;
+
+/// A set of hash functions computed together over the same data, implemented for tuples of up to
+/// eight `Digest` types. `FileReader::hash_multi` uses this to compute several digests in one pass.
+pub trait MultiDigest {
+    /// The outputs of every hash function, as a tuple in the same order.
+    type Output;
+
+    /// Creates a fresh hasher for every hash function.
+    fn new() -> Self;
+
+    /// Feeds `data` to every hasher.
+    fn update(&mut self, data: &[u8]);
+
+    /// Finalizes every hasher, returning their outputs.
+    fn finalize(self) -> Self::Output;
+
+    /// Feeds `data` to every hasher a chunk at a time, so each chunk is read from memory once and
+    /// hashed by all of them while it is still in cache.
+    fn update_chunked(&mut self, data: &[u8])
+    where
+        Self: Sized,
+    {
+        for chunk in data.chunks(CHUNK_LEN) {
+            self.update(chunk);
+        }
+    }
+}
+
+macro_rules! impl_multi_digest {
+    ($($hasher:ident $var:ident),+) => {
+        impl<$($hasher: Digest),+> MultiDigest for ($($hasher,)+) {
+            type Output = ($(Output<$hasher>,)+);
+
+            fn new() -> Self {
+                ($($hasher::new(),)+)
+            }
+
+            fn update(&mut self, data: &[u8]) {
+                let ($($var,)+) = self;
+                $(Digest::update($var, data);)+
+            }
+
+            fn finalize(self) -> Self::Output {
+                let ($($var,)+) = self;
+                ($($var.finalize(),)+)
+            }
+        }
+    };
+}
+
+impl_multi_digest!(A a);
+impl_multi_digest!(A a, B b);
+impl_multi_digest!(A a, B b, C c);
+impl_multi_digest!(A a, B b, C c, D d);
+impl_multi_digest!(A a, B b, C c, D d, E e);
+impl_multi_digest!(A a, B b, C c, D d, E e, F f);
+impl_multi_digest!(A a, B b, C c, D d, E e, F f, G g);
+impl_multi_digest!(A a, B b, C c, D d, E e, F f, G g, H h);
+
+#[cfg(feature = "hash_encoding")]
+/// A hash function with the identifiers that Subresource Integrity strings and multihashes use to name it.
+pub trait NamedDigest: Digest {
+    /// The algorithm's name in Subresource Integrity strings, if SRI allows it.
+    const SRI_NAME: Option<&'static str>;
+    /// The algorithm's code in the multihash table.
+    const MULTIHASH_CODE: u64;
+}
+
+macro_rules! impl_named_digest {
+    ($($feature:literal $hasher:ty, $sri_name:expr, $multihash_code:expr;)*) => {
+        $(
+            #[cfg(all(feature = "hash_encoding", feature = $feature))]
+            impl NamedDigest for $hasher {
+                const SRI_NAME: Option<&'static str> = $sri_name;
+                const MULTIHASH_CODE: u64 = $multihash_code;
+            }
+        )*
+    };
+}
+
+impl_named_digest! {
+    "sha2" sha2::Sha224, None, 0x1013;
+    "sha2" sha2::Sha256, Some("sha256"), 0x12;
+    "sha2" sha2::Sha384, Some("sha384"), 0x20;
+    "sha2" sha2::Sha512, Some("sha512"), 0x13;
+    "sha3_256" sha3::Sha3_224, None, 0x17;
+    "sha3_256" sha3::Sha3_256, None, 0x16;
+    "sha3_256" sha3::Sha3_384, None, 0x15;
+    "sha3_256" sha3::Sha3_512, None, 0x14;
+    "sha3_256" sha3::Keccak256, None, 0x1b;
+}
+
+#[cfg(feature = "hash_encoding")]
+/// The names Subresource Integrity strings may use, recognized by `parse_hash_str`.
+const SRI_NAMES: [&str; 3] = ["sha256", "sha384", "sha512"];
+
+#[cfg(feature = "hash_encoding")]
+/// Decodes base64 with or without padding.
+const BASE64: GeneralPurpose = GeneralPurpose::new(
+    &alphabet::STANDARD,
+    GeneralPurposeConfig::new().with_decode_padding_mode(DecodePaddingMode::Indifferent),
+);
+#[cfg(feature = "hash_encoding")]
+const BASE64_URL: GeneralPurpose = GeneralPurpose::new(
+    &alphabet::URL_SAFE,
+    GeneralPurposeConfig::new().with_decode_padding_mode(DecodePaddingMode::Indifferent),
+);
+
+#[cfg(feature = "hash_encoding")]
+/// A text encoding of a hash.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum HashEncoding {
+    /// Lowercase hexadecimal, as printed by `sha256sum`.
+    #[default]
+    HexLower,
+    /// Uppercase hexadecimal.
+    HexUpper,
+    /// Standard base64 with padding.
+    Base64,
+    /// URL-safe base64 without padding.
+    Base64Url,
+    /// A Subresource Integrity string such as `sha384-<base64>`, as used by HTML and npm lockfiles.
+    Sri,
+    /// A multihash in base58btc multibase form, such as `z...`.
+    Multihash,
+}
+
+#[cfg(feature = "hash_encoding")]
+fn invalid_data(msg: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
+
+/// Encodes bytes as hexadecimal.
+pub(crate) fn to_hex(bytes: &[u8], upper: bool) -> String {
+    let digits = match upper {
+        true => b"0123456789ABCDEF",
+        false => b"0123456789abcdef",
+    };
+    let mut hex = String::with_capacity(bytes.len() * 2);
+    for byte in bytes {
+        hex.push(digits[usize::from(byte >> 4)] as char);
+        hex.push(digits[usize::from(byte & 0xf)] as char);
+    }
+    hex
+}
+
+#[cfg(feature = "hash_encoding")]
+fn from_hex(hex: &str) -> Option<Vec<u8>> {
+    if !hex.len().is_multiple_of(2) || !hex.bytes().all(|byte| byte.is_ascii_hexdigit()) {
+        return None;
+    }
+    hex.as_bytes()
+        .chunks(2)
+        .map(|pair| u8::from_str_radix(std::str::from_utf8(pair).ok()?, 16).ok())
+        .collect()
+}
+
+#[cfg(feature = "hash_encoding")]
+/// Converts decoded bytes into a digest output, failing if the length is wrong.
+fn to_output<H: Digest>(bytes: &[u8]) -> io::Result<Output<H>> {
+    match bytes.len() == <H as Digest>::output_size() {
+        true => Ok(Output::<H>::clone_from_slice(bytes)),
+        false => Err(invalid_data(format!(
+            "hash is {} bytes, expected {}",
+            bytes.len(),
+            <H as Digest>::output_size()
+        ))),
+    }
+}
+
+#[cfg(feature = "hash_encoding")]
+/// Decodes a multihash's bytes, checking that it names `H`.
+fn from_multihash<H: NamedDigest>(bytes: &[u8]) -> io::Result<Output<H>> {
+    let (code, code_len) = uvarint_at(bytes, 0)?;
+    let (len, len_len) = uvarint_at(bytes, code_len)?;
+    if code != H::MULTIHASH_CODE {
+        return Err(invalid_data(format!(
+            "multihash code {:#x} does not match {:#x}",
+            code,
+            H::MULTIHASH_CODE
+        )));
+    }
+    let digest = &bytes[code_len + len_len..];
+    if digest.len() as u64 != len {
+        return Err(invalid_data("multihash length is wrong".to_string()));
+    }
+    to_output::<H>(digest)
+}
+
+#[cfg(feature = "hash_encoding")]
+impl HashEncoding {
+    /// Encodes a digest of `H`.
+    /// Fails with `InvalidInput` for `Sri` if Subresource Integrity does not allow `H`.
+    pub fn encode<H: NamedDigest>(&self, digest: &Output<H>) -> io::Result<String> {
+        Ok(match self {
+            HashEncoding::HexLower => to_hex(digest, false),
+            HashEncoding::HexUpper => to_hex(digest, true),
+            HashEncoding::Base64 => base64::engine::general_purpose::STANDARD.encode(digest),
+            HashEncoding::Base64Url => {
+                base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(digest)
+            }
+            HashEncoding::Sri => {
+                let name = H::SRI_NAME.ok_or_else(|| {
+                    io::Error::new(
+                        io::ErrorKind::InvalidInput,
+                        "Subresource Integrity does not support this hash function",
+                    )
+                })?;
+                format!(
+                    "{}-{}",
+                    name,
+                    base64::engine::general_purpose::STANDARD.encode(digest)
+                )
+            }
+            HashEncoding::Multihash => {
+                let mut bytes = Vec::with_capacity(digest.len() + 4);
+                let (code, code_len) = encode_uvarint(H::MULTIHASH_CODE);
+                bytes.extend_from_slice(&code[..code_len]);
+                let (len, len_len) = encode_uvarint(digest.len() as u64);
+                bytes.extend_from_slice(&len[..len_len]);
+                bytes.extend_from_slice(digest);
+                format!("z{}", bs58::encode(bytes).into_string())
+            }
+        })
+    }
+
+    /// Decodes a digest of `H` from this encoding. Hex is accepted in either case and base64 with or
+    /// without padding. SRI strings may list several hashes, of which the one for `H` is used.
+    /// Multihashes may be in base58btc (`z`), base16 (`f`) or base64 (`m`, `u`) multibase form, or bare base58.
+    /// Fails with `InvalidData` if the string is malformed or names a different hash function.
+    pub fn decode<H: NamedDigest>(&self, s: &str) -> io::Result<Output<H>> {
+        let s = s.trim();
+        let malformed = || invalid_data(format!("malformed {:?} hash string", self));
+        match self {
+            HashEncoding::HexLower | HashEncoding::HexUpper => {
+                to_output::<H>(&from_hex(s).ok_or_else(malformed)?)
+            }
+            HashEncoding::Base64 => to_output::<H>(&BASE64.decode(s).map_err(|_| malformed())?),
+            HashEncoding::Base64Url => {
+                to_output::<H>(&BASE64_URL.decode(s).map_err(|_| malformed())?)
+            }
+            HashEncoding::Sri => {
+                let name = H::SRI_NAME.ok_or_else(malformed)?;
+                let token = s
+                    .split_ascii_whitespace()
+                    .filter_map(|token| token.split_once('-'))
+                    .find(|(token_name, _)| *token_name == name)
+                    .ok_or_else(|| invalid_data(format!("no {} hash in SRI string", name)))?;
+                let value = token.1.split('?').next().unwrap_or_default();
+                to_output::<H>(&BASE64.decode(value).map_err(|_| malformed())?)
+            }
+            HashEncoding::Multihash => {
+                let bytes = match s.split_at_checked(1) {
+                    Some(("z", rest)) => bs58::decode(rest).into_vec().ok(),
+                    Some(("f" | "F", rest)) => from_hex(rest),
+                    Some(("m", rest)) => BASE64.decode(rest).ok(),
+                    Some(("u", rest)) => BASE64_URL.decode(rest).ok(),
+                    _ => None,
+                }
+                .or_else(|| bs58::decode(s).into_vec().ok())
+                .ok_or_else(malformed)?;
+                from_multihash::<H>(&bytes)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "hash_encoding")]
+/// Parses a digest of `H` in any `HashEncoding`, detecting the encoding from the string.
+/// Fails with `InvalidData` if no encoding yields a digest of `H`.
+pub fn parse_hash_str<H: NamedDigest>(s: &str) -> io::Result<Output<H>> {
+    let s = s.trim();
+    let is_sri = s
+        .split_ascii_whitespace()
+        .filter_map(|token| token.split_once('-'))
+        .any(|(name, _)| SRI_NAMES.contains(&name));
+    if is_sri {
+        return HashEncoding::Sri.decode::<H>(s);
+    }
+    [
+        HashEncoding::HexLower,
+        HashEncoding::Base64,
+        HashEncoding::Base64Url,
+        HashEncoding::Multihash,
+    ]
+    .into_iter()
+    .find_map(|encoding| encoding.decode::<H>(s).ok())
+    .ok_or_else(|| invalid_data("unrecognized hash string".to_string()))
+}
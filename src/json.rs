@@ -0,0 +1,463 @@
+use crate::{FileReader, FileWriter};
+use std::{io, ops::Range, path::Path};
+
+/// Returns an `InvalidData` error for malformed JSON at the given offset.
+fn invalid_json(message: &str, offset: usize) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("invalid JSON at byte {}: {}", offset, message),
+    )
+}
+
+/// Returns a `NotFound` error for a JSON Pointer that does not resolve.
+fn pointer_not_found(pointer: &str) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::NotFound,
+        format!("JSON Pointer {:?} does not resolve to a value", pointer),
+    )
+}
+
+/// The maximum nesting depth of objects and arrays, bounding the scanner's recursion.
+const MAX_DEPTH: usize = 128;
+
+/// A member of a JSON object or an element of a JSON array.
+#[derive(Clone, Debug)]
+struct Child {
+    /// The decoded key of an object member; `None` for array elements.
+    key: Option<String>,
+    /// Range of the member, from the start of its key (or value, for array elements) to the end of its value.
+    member: Range<usize>,
+    /// Range of the value.
+    value: Range<usize>,
+}
+
+/// The members or elements of a JSON object or array.
+#[derive(Clone, Debug)]
+struct Container {
+    is_object: bool,
+    /// Offset of the opening bracket.
+    open: usize,
+    /// Offset of the closing bracket.
+    close: usize,
+    children: Vec<Child>,
+}
+
+/// A scanner over JSON text that tracks the byte spans of values without building a document tree.
+struct Scanner<'a> {
+    bytes: &'a [u8],
+}
+
+impl Scanner<'_> {
+    /// Returns the offset of the first non-whitespace byte at or after `offset`.
+    fn skip_ws(&self, mut offset: usize) -> usize {
+        while offset < self.bytes.len()
+            && matches!(self.bytes[offset], b' ' | b'\t' | b'\n' | b'\r')
+        {
+            offset += 1;
+        }
+        offset
+    }
+
+    /// Returns the byte at `offset`, or an error if the text ends first.
+    fn byte_at(&self, offset: usize) -> io::Result<u8> {
+        self.bytes
+            .get(offset)
+            .copied()
+            .ok_or_else(|| invalid_json("unexpected end of input", offset))
+    }
+
+    /// Returns the offset just past the string starting at `offset`.
+    fn string_end(&self, offset: usize) -> io::Result<usize> {
+        let mut i = offset + 1;
+        loop {
+            match self.byte_at(i)? {
+                b'"' => return Ok(i + 1),
+                b'\\' => i += 2,
+                _ => i += 1,
+            }
+        }
+    }
+
+    /// Returns the offset just past the run of ASCII digits starting at `offset`.
+    fn digits_end(&self, offset: usize) -> usize {
+        self.bytes[offset..]
+            .iter()
+            .position(|byte| !byte.is_ascii_digit())
+            .map_or(self.bytes.len(), |len| offset + len)
+    }
+
+    /// Returns the offset just past the number starting at `offset`, following the RFC 8259 grammar.
+    fn number_end(&self, offset: usize) -> io::Result<usize> {
+        let mut i = offset;
+        if self.bytes.get(i) == Some(&b'-') {
+            i += 1;
+        }
+        match self.byte_at(i)? {
+            b'0' => i += 1,
+            b'1'..=b'9' => i = self.digits_end(i),
+            _ => return Err(invalid_json("expected a digit", i)),
+        }
+        if self.bytes.get(i) == Some(&b'.') {
+            let fraction_end = self.digits_end(i + 1);
+            if fraction_end == i + 1 {
+                return Err(invalid_json("expected a digit", i + 1));
+            }
+            i = fraction_end;
+        }
+        if matches!(self.bytes.get(i), Some(b'e' | b'E')) {
+            i += 1;
+            if matches!(self.bytes.get(i), Some(b'+' | b'-')) {
+                i += 1;
+            }
+            let exponent_end = self.digits_end(i);
+            if exponent_end == i {
+                return Err(invalid_json("expected a digit", i));
+            }
+            i = exponent_end;
+        }
+        Ok(i)
+    }
+
+    /// Returns the offset just past the value starting at `offset`.
+    /// `depth` is the number of containers enclosing the value.
+    fn value_end(&self, offset: usize, depth: usize) -> io::Result<usize> {
+        match self.byte_at(offset)? {
+            b'"' => self.string_end(offset),
+            b'{' | b'[' => Ok(self.container(offset, depth)?.close + 1),
+            b'-' | b'0'..=b'9' => self.number_end(offset),
+            _ => [&b"true"[..], b"false", b"null"]
+                .into_iter()
+                .find(|literal| self.bytes[offset..].starts_with(literal))
+                .map(|literal| offset + literal.len())
+                .ok_or_else(|| invalid_json("expected a value", offset)),
+        }
+    }
+
+    /// Scans the object or array starting at `offset`, recording the span of each of its children.
+    /// `depth` is the number of containers enclosing it; nesting deeper than `MAX_DEPTH` is rejected.
+    fn container(&self, offset: usize, depth: usize) -> io::Result<Container> {
+        if depth >= MAX_DEPTH {
+            return Err(invalid_json("nesting is too deep", offset));
+        }
+        let is_object = self.byte_at(offset)? == b'{';
+        let close_byte = if is_object { b'}' } else { b']' };
+        let mut children = Vec::new();
+        let mut i = self.skip_ws(offset + 1);
+        if self.byte_at(i)? == close_byte {
+            return Ok(Container {
+                is_object,
+                open: offset,
+                close: i,
+                children,
+            });
+        }
+        loop {
+            let member_start = i;
+            let key = if is_object {
+                if self.byte_at(i)? != b'"' {
+                    return Err(invalid_json("expected an object key", i));
+                }
+                let key_end = self.string_end(i)?;
+                let key = decode_string(&self.bytes[i + 1..key_end - 1])
+                    .ok_or_else(|| invalid_json("invalid string escape", i))?;
+                i = self.skip_ws(key_end);
+                if self.byte_at(i)? != b':' {
+                    return Err(invalid_json("expected ':'", i));
+                }
+                i = self.skip_ws(i + 1);
+                Some(key)
+            } else {
+                None
+            };
+            let value_end = self.value_end(i, depth + 1)?;
+            children.push(Child {
+                key,
+                member: member_start..value_end,
+                value: i..value_end,
+            });
+            i = self.skip_ws(value_end);
+            match self.byte_at(i)? {
+                b',' => i = self.skip_ws(i + 1),
+                byte if byte == close_byte => {
+                    return Ok(Container {
+                        is_object,
+                        open: offset,
+                        close: i,
+                        children,
+                    })
+                }
+                _ => return Err(invalid_json("expected ',' or a closing bracket", i)),
+            }
+        }
+    }
+
+    /// Returns the range of the document's root value.
+    fn root(&self) -> io::Result<Range<usize>> {
+        let start = self.skip_ws(0);
+        let end = self.value_end(start, 0)?;
+        if self.skip_ws(end) != self.bytes.len() {
+            return Err(invalid_json("unexpected trailing content", end));
+        }
+        Ok(start..end)
+    }
+
+    /// Resolves the provided JSON Pointer tokens starting at the root value.
+    /// Returns the range of the value the pointer refers to.
+    fn resolve(&self, tokens: &[String]) -> io::Result<Option<Range<usize>>> {
+        let mut value = self.root()?;
+        for token in tokens {
+            match self.byte_at(value.start)? {
+                b'{' | b'[' => {
+                    let container = self.container(value.start, 0)?;
+                    match container.position(token) {
+                        Some(position) => value = container.children[position].value.clone(),
+                        None => return Ok(None),
+                    }
+                }
+                _ => return Ok(None),
+            }
+        }
+        Ok(Some(value))
+    }
+}
+
+impl Container {
+    /// Returns the position of the child the provided JSON Pointer token refers to.
+    /// Where an object has duplicate keys, the last one is used, as most parsers do.
+    fn position(&self, token: &str) -> Option<usize> {
+        if self.is_object {
+            self.children
+                .iter()
+                .rposition(|child| child.key.as_deref() == Some(token))
+        } else {
+            array_index(token).filter(|index| *index < self.children.len())
+        }
+    }
+
+    /// Returns the range to remove to delete the child at the provided position, including one adjoining comma.
+    fn removal_range(&self, position: usize) -> Range<usize> {
+        let child = &self.children[position];
+        match (position.checked_sub(1), self.children.get(position + 1)) {
+            (_, Some(next)) => child.member.start..next.member.start,
+            (Some(previous), None) => self.children[previous].value.end..child.member.end,
+            (None, None) => self.open + 1..self.close,
+        }
+    }
+}
+
+/// Parses a JSON Pointer array index token, rejecting leading zeros as RFC 6901 requires.
+fn array_index(token: &str) -> Option<usize> {
+    if token.is_empty() || (token.len() > 1 && token.starts_with('0')) {
+        return None;
+    }
+    token
+        .bytes()
+        .all(|byte| byte.is_ascii_digit())
+        .then(|| token.parse().ok())?
+}
+
+/// Splits a JSON Pointer into its unescaped reference tokens.
+fn parse_pointer(pointer: &str) -> io::Result<Vec<String>> {
+    if pointer.is_empty() {
+        return Ok(Vec::new());
+    }
+    let Some(pointer) = pointer.strip_prefix('/') else {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("JSON Pointer {:?} must be empty or start with '/'", pointer),
+        ));
+    };
+    Ok(pointer
+        .split('/')
+        .map(|token| token.replace("~1", "/").replace("~0", "~"))
+        .collect())
+}
+
+/// Reads the four hex digits of a `\u` escape as a UTF-16 code unit.
+fn code_unit(chars: &mut std::str::Chars) -> Option<u16> {
+    let digits = chars.by_ref().take(4).collect::<String>();
+    if digits.len() != 4 {
+        return None;
+    }
+    u16::from_str_radix(&digits, 16).ok()
+}
+
+/// Decodes the contents of a JSON string, excluding its quotes.
+fn decode_string(bytes: &[u8]) -> Option<String> {
+    let text = std::str::from_utf8(bytes).ok()?;
+    if !text.contains('\\') {
+        return Some(text.to_string());
+    }
+    let mut decoded = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            decoded.push(c);
+            continue;
+        }
+        let escaped = match chars.next()? {
+            'b' => '\u{8}',
+            'f' => '\u{c}',
+            'n' => '\n',
+            'r' => '\r',
+            't' => '\t',
+            'u' => {
+                let high = code_unit(&mut chars)?;
+                if (0xD800..0xDC00).contains(&high) {
+                    if (chars.next()?, chars.next()?) != ('\\', 'u') {
+                        return None;
+                    }
+                    char::decode_utf16([high, code_unit(&mut chars)?])
+                        .next()?
+                        .ok()?
+                } else {
+                    char::from_u32(high as u32)?
+                }
+            }
+            c => c,
+        };
+        decoded.push(escaped);
+    }
+    Some(decoded)
+}
+
+/// Encodes the provided text as a JSON string, including its quotes.
+fn encode_string(text: &str) -> String {
+    let mut encoded = String::with_capacity(text.len() + 2);
+    encoded.push('"');
+    for c in text.chars() {
+        match c {
+            '"' => encoded.push_str("\\\""),
+            '\\' => encoded.push_str("\\\\"),
+            '\n' => encoded.push_str("\\n"),
+            '\r' => encoded.push_str("\\r"),
+            '\t' => encoded.push_str("\\t"),
+            c if (c as u32) < 0x20 => encoded.push_str(&format!("\\u{:04x}", c as u32)),
+            c => encoded.push(c),
+        }
+    }
+    encoded.push('"');
+    encoded
+}
+
+/// Finds the byte span of the value the JSON Pointer refers to in the provided JSON text.
+fn json_get(bytes: &[u8], pointer: &str) -> io::Result<Range<usize>> {
+    let tokens = parse_pointer(pointer)?;
+    Scanner { bytes }
+        .resolve(&tokens)?
+        .ok_or_else(|| pointer_not_found(pointer))
+}
+
+impl<P: AsRef<Path> + Send + Sync> FileReader<P> {
+    /// Returns the byte range of the JSON value the provided JSON Pointer (RFC 6901) refers to in the file.
+    /// The file is scanned in place without building a document tree.
+    /// Fails with `NotFound` if the pointer does not resolve, or `InvalidData` if the file is not valid JSON.
+    pub fn json_get(&self, pointer: &str) -> io::Result<Range<usize>> {
+        json_get(self.as_slice(), pointer)
+    }
+}
+
+impl<P: AsRef<Path> + Send + Sync> FileWriter<P> {
+    /// Returns the byte range of the JSON value the provided JSON Pointer (RFC 6901) refers to in the file.
+    /// The file is scanned in place without building a document tree.
+    /// Fails with `NotFound` if the pointer does not resolve, or `InvalidData` if the file is not valid JSON.
+    pub fn json_get(&self, pointer: &str) -> io::Result<Range<usize>> {
+        json_get(self.bytes(), pointer)
+    }
+
+    /// Sets the JSON value the provided JSON Pointer refers to, splicing only that value's bytes.
+    /// `value` must be serialized JSON text.
+    /// If the pointer refers to a missing member of an existing object, the member is added after the
+    /// object's last member, copying its indentation and key separator. A final token of `-` or the
+    /// array's length appends to an array. All other formatting in the file is left intact.
+    pub fn json_set<B: AsRef<[u8]>>(&mut self, pointer: &str, value: B) -> io::Result<&Self> {
+        let value = value.as_ref();
+        let value_scanner = Scanner { bytes: value };
+        value_scanner.root()?;
+        let tokens = parse_pointer(pointer)?;
+        let scanner = Scanner {
+            bytes: self.bytes(),
+        };
+        if let Some(range) = scanner.resolve(&tokens)? {
+            self.splice_ranges(&[(range, value)])?;
+            return Ok(self);
+        }
+        let (last, parent_tokens) = tokens
+            .split_last()
+            .ok_or_else(|| pointer_not_found(pointer))?;
+        let parent = scanner
+            .resolve(parent_tokens)?
+            .filter(|parent| matches!(scanner.bytes[parent.start], b'{' | b'['))
+            .ok_or_else(|| pointer_not_found(pointer))?;
+        let container = scanner.container(parent.start, 0)?;
+        if !container.is_object
+            && last != "-"
+            && array_index(last) != Some(container.children.len())
+        {
+            return Err(pointer_not_found(pointer));
+        }
+        let key = container.is_object.then(|| encode_string(last));
+        let mut insertion = Vec::new();
+        let offset = match container.children.last() {
+            Some(previous) => {
+                let indent_start = container
+                    .children
+                    .len()
+                    .checked_sub(2)
+                    .map_or(container.open + 1, |n| container.children[n].value.end);
+                let gap = &scanner.bytes[indent_start..previous.member.start];
+                let indent = match gap.iter().position(|byte| *byte == b',') {
+                    Some(comma) => &gap[comma + 1..],
+                    None => gap,
+                };
+                insertion.push(b',');
+                insertion.extend_from_slice(indent);
+                if let Some(key) = key {
+                    let key_end = scanner.string_end(previous.member.start)?;
+                    insertion.extend_from_slice(key.as_bytes());
+                    insertion.extend_from_slice(&scanner.bytes[key_end..previous.value.start]);
+                }
+                previous.value.end
+            }
+            None => {
+                if let Some(key) = key {
+                    insertion.extend_from_slice(key.as_bytes());
+                    insertion.extend_from_slice(b": ");
+                }
+                container.open + 1
+            }
+        };
+        insertion.extend_from_slice(value);
+        self.splice_ranges(&[(offset..offset, insertion)])?;
+        Ok(self)
+    }
+
+    /// Removes the JSON value the provided JSON Pointer refers to, along with its key and one adjoining comma.
+    /// Returns false if the parent exists but the value does not.
+    /// Fails with `NotFound` if the parent does not exist, or `InvalidInput` for the root pointer.
+    pub fn json_remove(&mut self, pointer: &str) -> io::Result<bool> {
+        let tokens = parse_pointer(pointer)?;
+        let Some((last, parent_tokens)) = tokens.split_last() else {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "the root JSON value cannot be removed",
+            ));
+        };
+        let scanner = Scanner {
+            bytes: self.bytes(),
+        };
+        let parent = scanner
+            .resolve(parent_tokens)?
+            .filter(|parent| matches!(scanner.bytes[parent.start], b'{' | b'['))
+            .ok_or_else(|| pointer_not_found(pointer))?;
+        let container = scanner.container(parent.start, 0)?;
+        match container.position(last) {
+            Some(position) => {
+                let range = container.removal_range(position);
+                self.splice_ranges(&[(range, b"")])?;
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+}
@@ -0,0 +1,316 @@
+use crate::{
+    lines::{LineEnding, LineIndex},
+    FileWriter,
+};
+use std::{borrow::Cow, io, ops::Range, path::Path};
+
+/// The location of a key/value entry within its line.
+#[derive(Clone, Debug)]
+struct Entry {
+    key: String,
+    /// Range between the key and the value within the line, including the `=` but not any quote.
+    separator: Range<usize>,
+    /// Range of the value within the line, excluding surrounding whitespace and quotes.
+    value: Range<usize>,
+}
+
+/// A line of a key/value file, either as read from the file or as edited.
+#[derive(Clone, Debug)]
+struct KvLine {
+    /// Range of the line in the file, including its line ending.
+    /// Lines that have not been saved yet have an empty range at the offset they will be inserted at.
+    source: Range<usize>,
+    /// The new bytes of the line, including its line ending, if the line was edited, inserted or removed.
+    edit: Option<Vec<u8>>,
+    /// The section the line belongs to, or the section it opens if it is a section header.
+    section: Option<String>,
+    is_header: bool,
+    entry: Option<Entry>,
+}
+
+/// Parses a `[section]` header line, returning the section name.
+fn parse_header(line: &[u8]) -> Option<String> {
+    let trimmed = line.trim_ascii();
+    let name = trimmed.strip_prefix(b"[")?.strip_suffix(b"]")?;
+    Some(String::from_utf8_lossy(name.trim_ascii()).into_owned())
+}
+
+/// Returns the range of the value starting at `start`, excluding surrounding whitespace, quotes and
+/// any inline comment. A value in matching quotes ends at its closing quote; otherwise it ends at a
+/// `#` or `;` that follows whitespace.
+fn value_range(line: &[u8], start: usize) -> Range<usize> {
+    if let Some(&quote @ (b'"' | b'\'')) = line.get(start) {
+        let mut i = start + 1;
+        while i < line.len() {
+            match line[i] {
+                b'\\' if quote == b'"' => i += 2,
+                byte if byte == quote => return start + 1..i,
+                _ => i += 1,
+            }
+        }
+    }
+    let end = (start..line.len())
+        .find(|&i| matches!(line[i], b'#' | b';') && line[i - 1].is_ascii_whitespace())
+        .unwrap_or(line.len());
+    start..start + line[start..end].trim_ascii_end().len()
+}
+
+/// Parses a `key = value` line, also accepting the `export KEY=value` form used by `.env` files.
+/// Blank lines and lines starting with `#` or `;` are comments, as is anything after a `#` or `;`
+/// that follows whitespace outside quotes.
+fn parse_entry(line: &[u8]) -> Option<Entry> {
+    let line = line.trim_ascii_end();
+    let key_start = line.len() - line.trim_ascii_start().len();
+    if line.is_empty() || matches!(line[key_start], b'#' | b';' | b'[') {
+        return None;
+    }
+    let equals = line.iter().position(|byte| *byte == b'=')?;
+    let mut key = line[key_start..equals].trim_ascii();
+    if let Some(exported) = key.strip_prefix(b"export ") {
+        key = exported.trim_ascii_start();
+    }
+    if key.is_empty() {
+        return None;
+    }
+    let key_end = key_start + line[key_start..equals].trim_ascii_end().len();
+    let value_start = line.len() - line[equals + 1..].trim_ascii_start().len();
+    Some(Entry {
+        key: String::from_utf8_lossy(key).into_owned(),
+        separator: key_end..value_start,
+        value: value_range(line, value_start),
+    })
+}
+
+/// An editor for key/value configuration files such as `.env`, INI and `sysctl.conf` files.
+///
+/// Entries are `key = value` lines, optionally grouped under `[section]` headers; entries before
+/// the first header have no section. Lines starting with `#` or `;` are comments.
+/// Edits are held in memory and only touch the lines they change: comments, ordering, quoting and
+/// whitespace of all other lines are preserved. `save` writes all pending edits back through the
+/// `FileWriter` with a single resize.
+pub struct KvEditor<P: AsRef<Path> + Send + Sync> {
+    writer: FileWriter<P>,
+    lines: Vec<KvLine>,
+    ending: LineEnding,
+    /// The bytes between key and value used for new entries, taken from the first entry in the file.
+    separator: Vec<u8>,
+}
+
+impl<P: AsRef<Path> + Send + Sync> KvEditor<P> {
+    /// Parses the file of the provided `FileWriter` and returns an editor for it.
+    pub fn open(writer: FileWriter<P>) -> Self {
+        let mut editor = Self {
+            writer,
+            lines: Vec::new(),
+            ending: LineEnding::Lf,
+            separator: b"=".to_vec(),
+        };
+        editor.parse();
+        editor
+    }
+
+    /// Rebuilds the line model from the current contents of the file.
+    fn parse(&mut self) {
+        let bytes = self.writer.bytes();
+        let index = LineIndex::new(bytes);
+        self.ending = index.ending();
+        let mut section = None;
+        let mut separator = None;
+        self.lines = (0..index.len())
+            .map(|n| {
+                let source = index.line_with_ending(n).unwrap_or_default();
+                let line = &bytes[source.clone()];
+                let header = parse_header(line);
+                let is_header = header.is_some();
+                if is_header {
+                    section = header;
+                }
+                let entry = (!is_header).then(|| parse_entry(line)).flatten();
+                if let (None, Some(entry)) = (&separator, &entry) {
+                    separator = Some(line[entry.separator.clone()].to_vec());
+                }
+                KvLine {
+                    source,
+                    edit: None,
+                    section: section.clone(),
+                    is_header,
+                    entry,
+                }
+            })
+            .collect();
+        if let Some(separator) = separator {
+            self.separator = separator;
+        }
+    }
+
+    /// Returns the current bytes of a line, including its line ending.
+    fn line_bytes<'a>(&'a self, line: &'a KvLine) -> &'a [u8] {
+        match &line.edit {
+            Some(edit) => edit,
+            None => &self.writer.bytes()[line.source.clone()],
+        }
+    }
+
+    /// Returns the index of the last line holding `key` in `section`.
+    fn position(&self, section: Option<&str>, key: &str) -> Option<usize> {
+        self.lines.iter().rposition(|line| {
+            line.section.as_deref() == section
+                && line.entry.as_ref().is_some_and(|entry| entry.key == key)
+        })
+    }
+
+    /// Returns the value of `key` in `section`, with surrounding whitespace and quotes removed.
+    /// Pass `None` as the section for entries that precede any section header.
+    /// If the key occurs more than once, the last occurrence wins.
+    pub fn get(&self, section: Option<&str>, key: &str) -> Option<Cow<'_, str>> {
+        let line = &self.lines[self.position(section, key)?];
+        let value = line.entry.as_ref()?.value.clone();
+        Some(String::from_utf8_lossy(&self.line_bytes(line)[value]))
+    }
+
+    /// Sets the value of `key` in `section`.
+    /// If the key exists, only its value is replaced, keeping any quotes and inline comment around it. Otherwise a new
+    /// entry is inserted after the section's last line, creating the section at the end of the file
+    /// if it does not exist.
+    pub fn set(&mut self, section: Option<&str>, key: &str, value: &str) {
+        if let Some(n) = self.position(section, key) {
+            let line = &self.lines[n];
+            let range = line
+                .entry
+                .as_ref()
+                .map(|entry| entry.value.clone())
+                .unwrap_or_default();
+            let bytes = self.line_bytes(line);
+            // An empty value directly followed by an inline comment needs a space to keep the comment.
+            let gap = match bytes.get(range.end) {
+                Some(b'#' | b';') if range.is_empty() && !value.is_empty() => &b" "[..],
+                _ => b"",
+            };
+            let edit = [
+                &bytes[..range.start],
+                value.as_bytes(),
+                gap,
+                &bytes[range.end..],
+            ]
+            .concat();
+            let entry = parse_entry(&edit);
+            let line = &mut self.lines[n];
+            line.edit = Some(edit);
+            line.entry = entry;
+            return;
+        }
+        let entry_line = [key.as_bytes(), &self.separator, value.as_bytes()].concat();
+        let last_in_section = self.lines.iter().rposition(|line| {
+            line.section.as_deref() == section && (line.is_header || line.entry.is_some())
+        });
+        match (last_in_section, section) {
+            (Some(n), _) => self.insert_after(n, section, vec![entry_line]),
+            (None, None) => {
+                let first_header = self.lines.iter().position(|line| line.is_header);
+                let n = first_header.unwrap_or(self.lines.len());
+                self.insert_after(n.wrapping_sub(1), section, vec![entry_line]);
+            }
+            (None, Some(name)) => {
+                let mut new_lines = Vec::new();
+                if !self.lines.is_empty() {
+                    new_lines.push(Vec::new());
+                }
+                new_lines.push(format!("[{}]", name).into_bytes());
+                new_lines.push(entry_line);
+                self.insert_after(self.lines.len().wrapping_sub(1), section, new_lines);
+            }
+        }
+    }
+
+    /// Inserts the provided lines, which must not include line endings, after the nth line.
+    /// `usize::MAX` inserts at the start of the file.
+    fn insert_after(&mut self, n: usize, section: Option<&str>, new_lines: Vec<Vec<u8>>) {
+        let ending = self.ending.as_bytes();
+        let offset = match self.lines.get(n) {
+            Some(line) => {
+                let bytes = self.line_bytes(line);
+                if !bytes.is_empty() && !bytes.ends_with(b"\n") {
+                    let edit = [bytes, ending].concat();
+                    self.lines[n].edit = Some(edit);
+                }
+                self.lines[n].source.end
+            }
+            None => 0,
+        };
+        let insert_at = n.wrapping_add(1);
+        for (i, content) in new_lines.into_iter().enumerate() {
+            let header = parse_header(&content);
+            let entry = (header.is_none()).then(|| parse_entry(&content)).flatten();
+            self.lines.insert(
+                insert_at + i,
+                KvLine {
+                    source: offset..offset,
+                    edit: Some([&content[..], ending].concat()),
+                    section: section.map(str::to_string),
+                    is_header: header.is_some(),
+                    entry,
+                },
+            );
+        }
+    }
+
+    /// Removes every occurrence of `key` in `section`. Returns true if any entry was removed.
+    pub fn remove(&mut self, section: Option<&str>, key: &str) -> bool {
+        let mut removed = false;
+        for line in &mut self.lines {
+            if line.section.as_deref() == section
+                && line.entry.as_ref().is_some_and(|entry| entry.key == key)
+            {
+                line.edit = Some(Vec::new());
+                line.entry = None;
+                removed = true;
+            }
+        }
+        removed
+    }
+
+    /// Returns an iterator over every entry as `(section, key, value)`, in file order.
+    pub fn iter(&self) -> impl Iterator<Item = (Option<&str>, &str, Cow<'_, str>)> + '_ {
+        self.lines.iter().filter_map(|line| {
+            let entry = line.entry.as_ref()?;
+            let value = String::from_utf8_lossy(&self.line_bytes(line)[entry.value.clone()]);
+            Some((line.section.as_deref(), entry.key.as_str(), value))
+        })
+    }
+
+    /// Returns the names of every section, in file order.
+    pub fn sections(&self) -> impl Iterator<Item = &str> + '_ {
+        self.lines
+            .iter()
+            .filter(|line| line.is_header && line.edit.as_deref() != Some(b""))
+            .filter_map(|line| line.section.as_deref())
+    }
+
+    /// Returns true if there are edits that have not been saved.
+    pub fn is_dirty(&self) -> bool {
+        self.lines.iter().any(|line| line.edit.is_some())
+    }
+
+    /// Writes all pending edits to the file, resizing it at most once.
+    pub fn save(&mut self) -> io::Result<()> {
+        let edits = self
+            .lines
+            .iter()
+            .filter_map(|line| Some((line.source.clone(), line.edit.as_deref()?)))
+            .collect::<Vec<(Range<usize>, &[u8])>>();
+        self.writer.splice_ranges(&edits)?;
+        self.parse();
+        Ok(())
+    }
+
+    /// Returns a reference to the underlying `FileWriter`. Unsaved edits are not reflected in it.
+    pub fn writer(&self) -> &FileWriter<P> {
+        &self.writer
+    }
+
+    /// Saves pending edits and returns the underlying `FileWriter`.
+    pub fn into_writer(mut self) -> io::Result<FileWriter<P>> {
+        self.save()?;
+        Ok(self.writer)
+    }
+}
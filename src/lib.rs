@@ -3,8 +3,23 @@
 //! `file_rw` is a Rust library for efficient file reading and writing.
 //!
 //! It provides the following modules:
+//! - `binary`: Endian-aware and varint values at offsets, and a byte cursor
+//! - `bits`: Bit-level reading and writing
+//! - `cdc`: Content-defined chunking with FastCDC
+//! - `chunk`: Tagged, length-prefixed chunk formats such as RIFF, PNG and IFF
+//! - `checksum`: Non-cryptographic checksums such as CRC32C and xxHash
+//! - `csv`: Zero-copy CSV/TSV record iteration
 //! - `file`: File operations
+//! - `hash`: Computing several digests in one pass, and hash string encodings with the `hash_encoding` feature
+//! - `kv`: Format-preserving key/value config file editing
+//! - `lines`: Line indexing and line-oriented editing
+//! - `manifest`: Checksum manifests in `sha256sum` and BSD styles
+//! - `merkle`: Merkle trees over file chunks for partial verification and diffing
 //! - `read`: File reading capabilities
+//! - `record`: Fixed-size record files with slot reuse
+//! - `serialize`: Serde integration and JSON Lines records
+//! - `signature`: Ed25519 file signatures and detached `.sig` files
+//! - `template`: Placeholder template rendering
 //! - `write`: File writing capabilities
 //!
 //! ## Reexports
@@ -122,12 +137,86 @@
 //!   assert_eq!(reader.hash(), sha3_direct_hasher.finalize());
 //! }
 //! ```
+//!
+//! ### BLAKE3 Hashing
+//! Use the `blake3` feature to hash files with BLAKE3, spreading large files across threads.
+//! ```rust
+//! use file_rw::{FileReader, FileWriter};
+//! use tempfile::tempdir;
+//!
+//! let tempdir = tempdir().unwrap();
+//! let tempdir_path = tempdir.path();
+//! let test_path = tempdir_path.join("test.txt");
+//! let mut writer = FileWriter::open(&test_path).unwrap();
+//!
+//! writer.overwrite("Hello World!");
+//! let reader = writer.as_reader().unwrap();
+//!
+//! #[cfg(feature = "blake3")]
+//! {
+//!   assert_eq!(reader.blake3(), blake3::hash(b"Hello World!"));
+//! }
+//! ```
+//!
+//! ### JSON Editing
+//! Use the `json` feature to locate and edit JSON values by JSON Pointer, leaving the rest of the
+//! file's formatting untouched.
+//! ```rust
+//! use file_rw::FileWriter;
+//! use tempfile::tempdir;
+//!
+//! let tempdir = tempdir().unwrap();
+//! let tempdir_path = tempdir.path();
+//! let test_path = tempdir_path.join("package.json");
+//! let mut writer = FileWriter::open(&test_path).unwrap();
+//! writer.overwrite("{\n  \"version\": \"0.1.0\"\n}\n");
+//!
+//! #[cfg(feature = "json")]
+//! {
+//!   writer.json_set("/version", "\"0.2.0\"").unwrap();
+//!   writer.json_set("/private", "true").unwrap();
+//!   assert_eq!(writer.bytes(), b"{\n  \"version\": \"0.2.0\",\n  \"private\": true\n}\n");
+//! }
+//! ```
 
 #![crate_name = "file_rw"]
 #![crate_type = "lib"]
 
+#[cfg(feature = "rkyv")]
+mod archive;
+pub mod binary;
+pub mod bits;
+#[cfg(feature = "cdc")]
+pub mod cdc;
+#[cfg(feature = "checksum")]
+pub mod checksum;
+pub mod chunk;
+#[cfg(feature = "csv")]
+pub mod csv;
 pub mod file; //mainly pub for use in tests
+#[cfg(feature = "hash")]
+pub mod hash;
+#[cfg(feature = "json")]
+mod json;
+pub mod kv;
+pub mod lines;
+#[cfg(feature = "mac")]
+mod mac;
+#[cfg(feature = "manifest")]
+pub mod manifest;
+#[cfg(feature = "hash")]
+pub mod merkle;
+#[cfg(feature = "bytemuck")]
+mod pod;
 pub mod read;
+#[cfg(feature = "bytemuck")]
+pub mod record;
+#[cfg(feature = "serde")]
+pub mod serialize;
+#[cfg(feature = "signature")]
+pub mod signature;
+#[cfg(feature = "search")]
+pub mod template;
 pub mod utils;
 mod write;
 
@@ -0,0 +1,431 @@
+use crate::{FileReader, FileWriter};
+use std::{
+    borrow::Cow,
+    io,
+    ops::{Bound, Range, RangeBounds},
+    path::Path,
+};
+
+#[cfg(feature = "search")]
+/// Returns the offsets of every `\n` byte in the given data.
+pub(crate) fn newline_offsets(bytes: &[u8]) -> impl Iterator<Item = usize> + '_ {
+    memchr::memchr_iter(b'\n', bytes)
+}
+
+#[cfg(not(feature = "search"))]
+/// Returns the offsets of every `\n` byte in the given data.
+pub(crate) fn newline_offsets(bytes: &[u8]) -> impl Iterator<Item = usize> + '_ {
+    bytes
+        .iter()
+        .enumerate()
+        .filter_map(|(offset, byte)| (*byte == b'\n').then_some(offset))
+}
+
+/// The line ending style of a file.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum LineEnding {
+    /// Unix-style `\n` line endings.
+    #[default]
+    Lf,
+    /// Windows-style `\r\n` line endings.
+    CrLf,
+}
+
+impl LineEnding {
+    /// Returns the bytes of the line ending.
+    pub fn as_bytes(&self) -> &'static [u8] {
+        match self {
+            LineEnding::Lf => b"\n",
+            LineEnding::CrLf => b"\r\n",
+        }
+    }
+}
+
+/// A single line of a `LineIndex`, stored as offsets into the indexed data.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Line {
+    start: usize,
+    content_end: usize,
+    end: usize,
+}
+
+/// An index of the lines in a byte slice, such as the contents of a `FileReader` or `FileWriter`.
+/// A line is terminated by `\n` or `\r\n`; the final line need not be terminated.
+/// The index stores offsets only, so it must be rebuilt after the indexed data changes.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct LineIndex {
+    lines: Vec<Line>,
+    ending: LineEnding,
+}
+
+impl LineIndex {
+    /// Builds a line index over the provided bytes.
+    /// The line ending style is taken from the first terminated line, defaulting to `\n`.
+    pub fn new(bytes: &[u8]) -> Self {
+        let mut lines = Vec::new();
+        let mut start = 0;
+        for newline in newline_offsets(bytes) {
+            let content_end = if newline > start && bytes[newline - 1] == b'\r' {
+                newline - 1
+            } else {
+                newline
+            };
+            lines.push(Line {
+                start,
+                content_end,
+                end: newline + 1,
+            });
+            start = newline + 1;
+        }
+        if start < bytes.len() {
+            lines.push(Line {
+                start,
+                content_end: bytes.len(),
+                end: bytes.len(),
+            });
+        }
+        let ending = match lines.first() {
+            Some(line) if line.end - line.content_end == 2 => LineEnding::CrLf,
+            _ => LineEnding::Lf,
+        };
+        Self { lines, ending }
+    }
+
+    /// Returns the number of lines.
+    pub fn len(&self) -> usize {
+        self.lines.len()
+    }
+
+    /// Returns true if there are no lines.
+    pub fn is_empty(&self) -> bool {
+        self.lines.is_empty()
+    }
+
+    /// Returns the line ending style of the indexed data.
+    pub fn ending(&self) -> LineEnding {
+        self.ending
+    }
+
+    /// Returns true if the last line is terminated by a line ending.
+    pub fn ends_with_newline(&self) -> bool {
+        self.lines
+            .last()
+            .is_some_and(|line| line.end > line.content_end)
+    }
+
+    /// Returns the range of the nth line's contents, excluding its line ending.
+    pub fn line(&self, n: usize) -> Option<Range<usize>> {
+        self.lines.get(n).map(|line| line.start..line.content_end)
+    }
+
+    /// Returns the range of the nth line, including its line ending.
+    pub fn line_with_ending(&self, n: usize) -> Option<Range<usize>> {
+        self.lines.get(n).map(|line| line.start..line.end)
+    }
+
+    /// Returns an iterator over the ranges of every line's contents, excluding line endings.
+    pub fn iter(&self) -> impl Iterator<Item = Range<usize>> + '_ {
+        self.lines.iter().map(|line| line.start..line.content_end)
+    }
+
+    /// Returns the index of the line containing the given offset.
+    pub fn line_at_offset(&self, offset: usize) -> Option<usize> {
+        let n = self.lines.partition_point(|line| line.end <= offset);
+        (n < self.lines.len()).then_some(n)
+    }
+}
+
+/// Returns an `InvalidInput` error describing a line number that is out of range.
+fn line_out_of_range(n: usize, len: usize) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidInput,
+        format!("line {} is out of range for a file of {} lines", n, len),
+    )
+}
+
+impl<P: AsRef<Path> + Send + Sync> FileReader<P> {
+    /// Builds a `LineIndex` over the file data.
+    pub fn line_index(&self) -> LineIndex {
+        LineIndex::new(self.as_slice())
+    }
+}
+
+impl<P: AsRef<Path> + Send + Sync> FileWriter<P> {
+    /// Builds a `LineIndex` over the file data.
+    pub fn line_index(&self) -> LineIndex {
+        LineIndex::new(self.bytes())
+    }
+
+    /// Replaces the contents of the nth line (zero-based), keeping its existing line ending.
+    /// `text` should not contain line endings.
+    pub fn replace_line<B: AsRef<[u8]>>(&mut self, n: usize, text: B) -> io::Result<&Self> {
+        let index = self.line_index();
+        let range = index
+            .line(n)
+            .ok_or_else(|| line_out_of_range(n, index.len()))?;
+        self.splice_ranges(&[(range, text.as_ref())])?;
+        Ok(self)
+    }
+
+    /// Inserts a line before the nth line (zero-based), using the file's line ending style.
+    /// Passing the number of lines in the file as `n` appends the line to the end of the file; if the
+    /// file did not end with a line ending, the inserted line will not either.
+    /// `text` should not contain line endings.
+    pub fn insert_line<B: AsRef<[u8]>>(&mut self, n: usize, text: B) -> io::Result<&Self> {
+        let index = self.line_index();
+        let ending = index.ending().as_bytes();
+        let text = text.as_ref();
+        let (offset, bytes) = match index.line(n) {
+            Some(range) => (range.start, [text, ending].concat()),
+            None if n == index.len() && !index.is_empty() && !index.ends_with_newline() => {
+                (self.len(), [ending, text].concat())
+            }
+            None if n == index.len() => (self.len(), [text, ending].concat()),
+            None => return Err(line_out_of_range(n, index.len())),
+        };
+        self.splice_ranges(&[(offset..offset, bytes)])?;
+        Ok(self)
+    }
+
+    /// Deletes the lines in the provided range of line numbers (zero-based), including their line endings.
+    pub fn delete_lines<R: RangeBounds<usize>>(&mut self, lines: R) -> io::Result<&Self> {
+        let index = self.line_index();
+        let start = match lines.start_bound() {
+            Bound::Included(&n) => n,
+            Bound::Excluded(&n) => n + 1,
+            Bound::Unbounded => 0,
+        };
+        let end = match lines.end_bound() {
+            Bound::Included(&n) => n + 1,
+            Bound::Excluded(&n) => n,
+            Bound::Unbounded => index.len(),
+        };
+        if start > end || end > index.len() {
+            return Err(line_out_of_range(end.max(start), index.len()));
+        }
+        if start < end {
+            let first = index.line_with_ending(start).unwrap_or_default();
+            let last = index.line_with_ending(end - 1).unwrap_or_default();
+            self.splice_ranges(&[(first.start..last.end, b"")])?;
+        }
+        Ok(self)
+    }
+
+    /// Keeps only the lines for which `keep` returns true, removing the others along with their line endings.
+    /// `keep` is passed each line's contents, excluding its line ending.
+    /// The file is resized at most once. Returns the number of lines removed.
+    pub fn retain_lines<F: FnMut(&[u8]) -> bool>(&mut self, mut keep: F) -> io::Result<usize> {
+        let index = self.line_index();
+        let mut edits: Vec<(Range<usize>, &[u8])> = Vec::new();
+        let mut removed = 0;
+        for n in 0..index.len() {
+            let content = index.line(n).unwrap_or_default();
+            if !keep(&self.mmap[content]) {
+                removed += 1;
+                let range = index.line_with_ending(n).unwrap_or_default();
+                match edits.last_mut() {
+                    Some((last, _)) if last.end == range.start => last.end = range.end,
+                    _ => edits.push((range, b"")),
+                }
+            }
+        }
+        self.splice_ranges(&edits)?;
+        Ok(removed)
+    }
+
+    /// Replaces the contents of each line with the output of `map`, keeping the existing line endings.
+    /// `map` is passed each line's contents, excluding its line ending, and may return the same
+    /// bytes borrowed to leave the line unchanged.
+    /// The file is resized at most once. Returns the number of lines changed.
+    pub fn map_lines<F>(&mut self, mut map: F) -> io::Result<usize>
+    where
+        F: for<'l> FnMut(&'l [u8]) -> Cow<'l, [u8]>,
+    {
+        let index = self.line_index();
+        let mut edits = Vec::new();
+        for content in index.iter() {
+            let line = &self.mmap[content.clone()];
+            let mapped = map(line);
+            if *mapped != *line {
+                edits.push((content, mapped.into_owned()));
+            }
+        }
+        self.splice_ranges(&edits)?;
+        Ok(edits.len())
+    }
+}
+
+/// A rule for matching a line of a file, used by `FileWriter::ensure_line`.
+/// Matching is performed against each line's contents, excluding its line ending.
+#[derive(Clone, Copy, Debug)]
+pub enum LineMatch<'a> {
+    /// Matches lines equal to the provided bytes.
+    Exact(&'a [u8]),
+    /// Matches lines starting with the provided bytes.
+    Prefix(&'a [u8]),
+    #[cfg(feature = "regex")]
+    /// Matches lines containing a match of the provided regular expression.
+    Regex(&'a regex::bytes::Regex),
+}
+
+impl LineMatch<'_> {
+    /// Returns true if the provided line contents match.
+    pub fn matches(&self, line: &[u8]) -> bool {
+        match self {
+            LineMatch::Exact(exact) => line == *exact,
+            LineMatch::Prefix(prefix) => line.starts_with(prefix),
+            #[cfg(feature = "regex")]
+            LineMatch::Regex(regex) => regex.is_match(line),
+        }
+    }
+
+    /// Returns the number of the last line in the index that matches.
+    fn rfind_in(&self, bytes: &[u8], index: &LineIndex) -> Option<usize> {
+        index
+            .iter()
+            .enumerate()
+            .filter(|(_, range)| self.matches(&bytes[range.clone()]))
+            .map(|(n, _)| n)
+            .last()
+    }
+}
+
+/// Where `FileWriter::ensure_line` inserts a line that was not already present.
+#[derive(Clone, Copy, Debug, Default)]
+pub enum LinePosition<'a> {
+    /// At the start of the file.
+    Start,
+    /// At the end of the file.
+    #[default]
+    End,
+    /// Before the last line matching the rule, or at the end of the file if no line matches.
+    Before(LineMatch<'a>),
+    /// After the last line matching the rule, or at the end of the file if no line matches.
+    After(LineMatch<'a>),
+}
+
+impl<P: AsRef<Path> + Send + Sync> FileWriter<P> {
+    /// Ensures the file contains `line`, idempotently.
+    /// If any line matches `matcher`, the last such line is replaced with `line`; otherwise, unless some
+    /// line already equals `line`, `line` is inserted at `position`. All other content is left byte-identical.
+    /// Returns true if the file was changed.
+    pub fn ensure_line<B: AsRef<[u8]>>(
+        &mut self,
+        line: B,
+        matcher: LineMatch,
+        position: LinePosition,
+    ) -> io::Result<bool> {
+        let line = line.as_ref();
+        let index = self.line_index();
+        if let Some(n) = matcher.rfind_in(self.bytes(), &index) {
+            let range = index.line(n).unwrap_or_default();
+            if &self.mmap[range] == line {
+                return Ok(false);
+            }
+            self.replace_line(n, line)?;
+            return Ok(true);
+        }
+        if LineMatch::Exact(line)
+            .rfind_in(self.bytes(), &index)
+            .is_some()
+        {
+            return Ok(false);
+        }
+        let n = match position {
+            LinePosition::Start => 0,
+            LinePosition::End => index.len(),
+            LinePosition::Before(anchor) => {
+                anchor.rfind_in(self.bytes(), &index).unwrap_or(index.len())
+            }
+            LinePosition::After(anchor) => anchor
+                .rfind_in(self.bytes(), &index)
+                .map_or(index.len(), |n| n + 1),
+        };
+        self.insert_line(n, line)?;
+        Ok(true)
+    }
+
+    /// Finds the line numbers of the first `begin_marker` line and the `end_marker` line that follows it.
+    /// Returns an `InvalidData` error if the begin marker is not followed by an end marker.
+    fn find_block(
+        &self,
+        index: &LineIndex,
+        begin_marker: &[u8],
+        end_marker: &[u8],
+    ) -> io::Result<Option<(usize, usize)>> {
+        let bytes = self.bytes();
+        let mut lines = index.iter().enumerate();
+        let Some((begin, _)) = lines.find(|(_, range)| &bytes[range.clone()] == begin_marker)
+        else {
+            return Ok(None);
+        };
+        match lines.find(|(_, range)| &bytes[range.clone()] == end_marker) {
+            Some((end, _)) => Ok(Some((begin, end))),
+            None => Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "block begin marker found without a matching end marker",
+            )),
+        }
+    }
+
+    /// Ensures the file contains a block of `content` lines between `begin_marker` and `end_marker` lines, idempotently.
+    /// If the markers are present, only the lines between them are replaced; otherwise the block is
+    /// appended to the end of the file. Every line of `content`, including the last, is terminated with
+    /// the file's line ending, whatever line endings `content` uses.
+    /// All content outside the block is left byte-identical.
+    /// Returns true if the file was changed.
+    pub fn upsert_block<BB: AsRef<[u8]>, BE: AsRef<[u8]>, BC: AsRef<[u8]>>(
+        &mut self,
+        begin_marker: BB,
+        end_marker: BE,
+        content: BC,
+    ) -> io::Result<bool> {
+        let (begin_marker, end_marker) = (begin_marker.as_ref(), end_marker.as_ref());
+        let index = self.line_index();
+        let ending = index.ending().as_bytes();
+        let content = content.as_ref();
+        let content = LineIndex::new(content)
+            .iter()
+            .flat_map(|line| [&content[line], ending])
+            .collect::<Vec<_>>()
+            .concat();
+        match self.find_block(&index, begin_marker, end_marker)? {
+            Some((begin, end)) => {
+                let start = index.line_with_ending(begin).unwrap_or_default().end;
+                let range = start..index.line(end).unwrap_or_default().start;
+                if self.mmap[range.clone()] == content[..] {
+                    return Ok(false);
+                }
+                self.splice_ranges(&[(range, content)])?;
+            }
+            None => {
+                let mut block = Vec::new();
+                if !index.is_empty() && !index.ends_with_newline() {
+                    block.extend_from_slice(ending);
+                }
+                for part in [begin_marker, ending, &content, end_marker, ending] {
+                    block.extend_from_slice(part);
+                }
+                self.append(block)?;
+            }
+        }
+        Ok(true)
+    }
+
+    /// Removes the block delimited by `begin_marker` and `end_marker` lines, including the markers, idempotently.
+    /// Returns true if the file was changed.
+    pub fn remove_block<BB: AsRef<[u8]>, BE: AsRef<[u8]>>(
+        &mut self,
+        begin_marker: BB,
+        end_marker: BE,
+    ) -> io::Result<bool> {
+        let index = self.line_index();
+        match self.find_block(&index, begin_marker.as_ref(), end_marker.as_ref())? {
+            Some((begin, end)) => {
+                self.delete_lines(begin..=end)?;
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+}
@@ -0,0 +1,124 @@
+use crate::FileReader;
+use digest::{KeyInit, Mac, Output};
+use std::{io, path::Path};
+
+#[cfg(feature = "sha3_256")]
+use digest::{ExtendableOutput, Update};
+#[cfg(feature = "sha3_256")]
+use hmac::Hmac;
+#[cfg(feature = "sha3_256")]
+use sha3::{CShake256, CShake256Core, Sha3_256};
+#[cfg(feature = "sha3_256")]
+use subtle::ConstantTimeEq;
+
+/// The rate of cSHAKE256 in bytes, which KMAC256 pads its key to.
+#[cfg(feature = "sha3_256")]
+const KMAC256_RATE: usize = 136;
+
+/// Encodes `x` as in NIST SP 800-185: its big-endian bytes, without leading zeros but at least one byte.
+#[cfg(feature = "sha3_256")]
+fn encode_bytes(x: u64) -> Vec<u8> {
+    let bytes = x.to_be_bytes();
+    let skip = bytes.iter().take_while(|byte| **byte == 0).count().min(7);
+    bytes[skip..].to_vec()
+}
+
+/// `left_encode` from NIST SP 800-185: the encoded length followed by the encoding of `x`.
+#[cfg(feature = "sha3_256")]
+fn left_encode(x: u64) -> Vec<u8> {
+    let bytes = encode_bytes(x);
+    let mut encoded = vec![bytes.len() as u8];
+    encoded.extend_from_slice(&bytes);
+    encoded
+}
+
+/// `right_encode` from NIST SP 800-185: the encoding of `x` followed by its length.
+#[cfg(feature = "sha3_256")]
+fn right_encode(x: u64) -> Vec<u8> {
+    let mut encoded = encode_bytes(x);
+    encoded.push(encoded.len() as u8);
+    encoded
+}
+
+/// Computes KMAC256 (NIST SP 800-185) of `data` with `output_len` bytes of output.
+#[cfg(feature = "sha3_256")]
+fn kmac256(key: &[u8], customization: &[u8], data: &[u8], output_len: usize) -> Vec<u8> {
+    let mut padded_key = left_encode(KMAC256_RATE as u64);
+    padded_key.extend_from_slice(&left_encode(key.len() as u64 * 8));
+    padded_key.extend_from_slice(key);
+    padded_key.resize(padded_key.len().next_multiple_of(KMAC256_RATE), 0);
+
+    let mut xof = CShake256::from_core(CShake256Core::new_with_function_name(
+        b"KMAC",
+        customization,
+    ));
+    xof.update(&padded_key);
+    xof.update(data);
+    xof.update(&right_encode(output_len as u64 * 8));
+    let mut output = vec![0; output_len];
+    xof.finalize_xof_into(&mut output);
+    output
+}
+
+impl<P: AsRef<Path> + Send + Sync> FileReader<P> {
+    /// Computes the message authentication code of the file data using the given MAC with a secret key,
+    /// such as `Hmac<Sha256>`. Fails with `InvalidInput` if the MAC does not accept a key of that length.
+    pub fn mac_with<M: Mac + KeyInit>(&self, key: &[u8]) -> io::Result<Output<M>> {
+        let mut mac = <M as KeyInit>::new_from_slice(key)
+            .map_err(|error| io::Error::new(io::ErrorKind::InvalidInput, error.to_string()))?;
+        Mac::update(&mut mac, self.as_slice());
+        Ok(mac.finalize().into_bytes())
+    }
+
+    /// Verifies a message authentication code of the file data using the given MAC with a secret key.
+    /// The tag is compared in constant time. Returns false if the tag doesn't match or the MAC does not
+    /// accept a key of that length.
+    pub fn verify_mac_with<M: Mac + KeyInit>(&self, key: &[u8], tag: &[u8]) -> bool {
+        let Ok(mut mac) = <M as KeyInit>::new_from_slice(key) else {
+            return false;
+        };
+        Mac::update(&mut mac, self.as_slice());
+        mac.verify_slice(tag).is_ok()
+    }
+
+    #[cfg(feature = "sha3_256")]
+    /// Computes the HMAC-SHA3-256 of the file data with a secret key.
+    pub fn hmac_sha3_256(&self, key: &[u8]) -> Output<Hmac<Sha3_256>> {
+        let mut mac = <Hmac<Sha3_256> as KeyInit>::new_from_slice(key)
+            .expect("HMAC accepts keys of any length");
+        Mac::update(&mut mac, self.as_slice());
+        mac.finalize().into_bytes()
+    }
+
+    #[cfg(feature = "sha3_256")]
+    /// Verifies an HMAC-SHA3-256 tag of the file data with a secret key, comparing in constant time.
+    pub fn verify_hmac_sha3_256(&self, key: &[u8], tag: &[u8]) -> bool {
+        self.verify_mac_with::<Hmac<Sha3_256>>(key, tag)
+    }
+
+    #[cfg(feature = "sha3_256")]
+    /// Computes the KMAC256 (NIST SP 800-185) of the file data with a secret key, producing `output_len` bytes.
+    /// `customization` separates uses of the same key and may be empty.
+    pub fn kmac256(&self, key: &[u8], customization: &[u8], output_len: usize) -> Vec<u8> {
+        kmac256(key, customization, self.as_slice(), output_len)
+    }
+
+    #[cfg(feature = "sha3_256")]
+    /// Verifies a KMAC256 tag of the file data with a secret key and customization string, comparing
+    /// in constant time. Returns false if the tag is not exactly `output_len` bytes or `output_len` is zero,
+    /// so a truncated or empty tag never verifies.
+    pub fn verify_kmac256(
+        &self,
+        key: &[u8],
+        customization: &[u8],
+        tag: &[u8],
+        output_len: usize,
+    ) -> bool {
+        if output_len == 0 || tag.len() != output_len {
+            return false;
+        }
+        kmac256(key, customization, self.as_slice(), output_len)
+            .ct_eq(tag)
+            .into()
+    }
+}
@@ -0,0 +1,237 @@
+use crate::FileReader;
+use std::{
+    fmt, io,
+    path::{Path, PathBuf},
+};
+
+/// The hash function of a checksum manifest.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ManifestAlgorithm {
+    /// SHA-256, as written by `sha256sum`.
+    Sha256,
+    /// SHA-512, as written by `sha512sum`.
+    Sha512,
+    /// BLAKE2b-512, as written by `b2sum`.
+    Blake2b512,
+    /// SHA3-256, as written by `cksum -a sha3 -l 256`.
+    Sha3_256,
+}
+
+impl ManifestAlgorithm {
+    /// Returns the tag naming the algorithm in BSD-style manifest lines.
+    pub fn bsd_tag(&self) -> &'static str {
+        match self {
+            ManifestAlgorithm::Sha256 => "SHA256",
+            ManifestAlgorithm::Sha512 => "SHA512",
+            ManifestAlgorithm::Blake2b512 => "BLAKE2b",
+            ManifestAlgorithm::Sha3_256 => "SHA3-256",
+        }
+    }
+
+    /// Returns the algorithm named by a BSD-style tag.
+    pub fn from_bsd_tag(tag: &str) -> Option<Self> {
+        [
+            ManifestAlgorithm::Sha256,
+            ManifestAlgorithm::Sha512,
+            ManifestAlgorithm::Blake2b512,
+            ManifestAlgorithm::Sha3_256,
+        ]
+        .into_iter()
+        .find(|algorithm| algorithm.bsd_tag() == tag)
+    }
+
+    /// Returns the length of the algorithm's digest in hex digits.
+    pub fn hex_len(&self) -> usize {
+        match self {
+            ManifestAlgorithm::Sha256 | ManifestAlgorithm::Sha3_256 => 64,
+            ManifestAlgorithm::Sha512 | ManifestAlgorithm::Blake2b512 => 128,
+        }
+    }
+
+    /// Guesses the algorithm of a GNU-style manifest from its file name, such as `SHA512SUMS` or
+    /// `archive.b2`, falling back to SHA-256 or SHA-512 by digest length if the name doesn't say or
+    /// names an algorithm of a different length.
+    fn guess(manifest_path: &Path, hex_len: usize) -> Option<Self> {
+        let name = manifest_path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_lowercase())
+            .unwrap_or_default();
+        let is_b2 = name
+            .split(|c: char| !c.is_ascii_alphanumeric())
+            .any(|token| token == "b2" || token.starts_with("b2sum"));
+        let guess = if name.contains("sha3") {
+            Some(ManifestAlgorithm::Sha3_256)
+        } else if name.contains("sha512") {
+            Some(ManifestAlgorithm::Sha512)
+        } else if name.contains("sha256") {
+            Some(ManifestAlgorithm::Sha256)
+        } else if name.contains("blake2") || is_b2 {
+            Some(ManifestAlgorithm::Blake2b512)
+        } else {
+            None
+        };
+        guess
+            .filter(|guess| guess.hex_len() == hex_len)
+            .or_else(|| {
+                [ManifestAlgorithm::Sha256, ManifestAlgorithm::Sha512]
+                    .into_iter()
+                    .find(|algorithm| algorithm.hex_len() == hex_len)
+            })
+    }
+
+    /// Computes the digest of the file data as a lowercase hex string.
+    pub fn hash_to_string<P: AsRef<Path> + Send + Sync>(&self, reader: &FileReader<P>) -> String {
+        match self {
+            ManifestAlgorithm::Sha256 => reader.hash_to_string_with::<sha2::Sha256>(),
+            ManifestAlgorithm::Sha512 => reader.hash_to_string_with::<sha2::Sha512>(),
+            ManifestAlgorithm::Blake2b512 => reader.hash_to_string_with::<blake2::Blake2b512>(),
+            ManifestAlgorithm::Sha3_256 => reader.hash_to_string_with::<sha3::Sha3_256>(),
+        }
+    }
+}
+
+/// The line format of a checksum manifest.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ManifestStyle {
+    /// GNU coreutils text mode: `<hex>  <path>`.
+    #[default]
+    Gnu,
+    /// GNU coreutils binary mode: `<hex> *<path>`.
+    GnuBinary,
+    /// BSD tagged style: `SHA256 (<path>) = <hex>`.
+    Bsd,
+}
+
+/// The result of verifying one file listed in a manifest.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ManifestStatus {
+    /// The file's digest matches the manifest.
+    Ok,
+    /// The file's digest differs from the manifest.
+    Mismatch,
+    /// The file does not exist.
+    Missing,
+}
+
+impl fmt::Display for ManifestStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            ManifestStatus::Ok => "OK",
+            ManifestStatus::Mismatch => "FAILED",
+            ManifestStatus::Missing => "MISSING",
+        })
+    }
+}
+
+/// A file listed in a manifest and the result of verifying it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ManifestEntry {
+    /// The path as written in the manifest.
+    pub path: PathBuf,
+    pub algorithm: ManifestAlgorithm,
+    pub status: ManifestStatus,
+}
+
+/// Escapes a file name the way GNU coreutils does, returning whether any escaping was needed.
+fn escape_name(name: &str) -> (bool, String) {
+    let escaped = name
+        .replace('\\', "\\\\")
+        .replace('\n', "\\n")
+        .replace('\r', "\\r");
+    (escaped.len() != name.len(), escaped)
+}
+
+fn unescape_name(name: &str) -> String {
+    let mut unescaped = String::with_capacity(name.len());
+    let mut chars = name.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            unescaped.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => unescaped.push('\n'),
+            Some('r') => unescaped.push('\r'),
+            Some(other) => unescaped.push(other),
+            None => unescaped.push('\\'),
+        }
+    }
+    unescaped
+}
+
+/// Formats one manifest line, without its line ending.
+pub(crate) fn format_line(
+    algorithm: ManifestAlgorithm,
+    style: ManifestStyle,
+    hex: &str,
+    path: &Path,
+) -> String {
+    let (escaped, name) = escape_name(&path.to_string_lossy());
+    let prefix = if escaped { "\\" } else { "" };
+    match style {
+        ManifestStyle::Gnu => format!("{}{}  {}", prefix, hex, name),
+        ManifestStyle::GnuBinary => format!("{}{} *{}", prefix, hex, name),
+        ManifestStyle::Bsd => format!("{}{} ({}) = {}", prefix, algorithm.bsd_tag(), name, hex),
+    }
+}
+
+/// Parses one manifest line into its algorithm, expected hex digest and path.
+/// Returns `None` for lines in neither style.
+fn parse_line(line: &str, manifest_path: &Path) -> Option<(ManifestAlgorithm, String, PathBuf)> {
+    let (escaped, line) = match line.strip_prefix('\\') {
+        Some(line) => (true, line),
+        None => (false, line),
+    };
+    let name = |name: &str| {
+        PathBuf::from(if escaped {
+            unescape_name(name)
+        } else {
+            name.to_string()
+        })
+    };
+    if let Some((tag, rest)) = line.split_once(" (") {
+        if let Some(algorithm) = ManifestAlgorithm::from_bsd_tag(tag) {
+            let (path, hex) = rest.rsplit_once(") = ")?;
+            return (hex.len() == algorithm.hex_len())
+                .then(|| (algorithm, hex.to_ascii_lowercase(), name(path)));
+        }
+    }
+    let (hex, rest) = line.split_once(' ')?;
+    let path = rest.strip_prefix(['*', ' '])?;
+    if !hex.bytes().all(|byte| byte.is_ascii_hexdigit()) || path.is_empty() {
+        return None;
+    }
+    let algorithm = ManifestAlgorithm::guess(manifest_path, hex.len())?;
+    Some((algorithm, hex.to_ascii_lowercase(), name(path)))
+}
+
+/// Verifies every file listed in the manifest text.
+pub(crate) fn verify(manifest: &str, manifest_path: &Path) -> io::Result<Vec<ManifestEntry>> {
+    let base = manifest_path.parent().unwrap_or(Path::new(""));
+    let mut entries = Vec::new();
+    for (n, line) in manifest.lines().enumerate() {
+        if line.trim().is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let (algorithm, expected, path) = parse_line(line, manifest_path).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("line {} is not a valid checksum line", n + 1),
+            )
+        })?;
+        let status = match FileReader::open(base.join(&path)) {
+            Ok(reader) => match algorithm.hash_to_string(&reader) == expected {
+                true => ManifestStatus::Ok,
+                false => ManifestStatus::Mismatch,
+            },
+            Err(error) if error.kind() == io::ErrorKind::NotFound => ManifestStatus::Missing,
+            Err(error) => return Err(error),
+        };
+        entries.push(ManifestEntry {
+            path,
+            algorithm,
+            status,
+        });
+    }
+    Ok(entries)
+}
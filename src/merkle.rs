@@ -0,0 +1,366 @@
+use crate::{FileReader, FileWriter};
+use digest::{Digest, Output};
+use std::{
+    ffi::OsString,
+    fmt, io,
+    ops::Range,
+    path::{Path, PathBuf},
+};
+
+/// The magic bytes identifying a persisted Merkle tree.
+const MERKLE_MAGIC: [u8; 8] = *b"FRWMRKL1";
+/// Domain separation prefixes for leaf and interior node hashes, as in RFC 6962.
+const LEAF_PREFIX: u8 = 0x00;
+const NODE_PREFIX: u8 = 0x01;
+
+fn hash_leaf<H: Digest>(chunk: &[u8]) -> Output<H> {
+    H::new()
+        .chain_update([LEAF_PREFIX])
+        .chain_update(chunk)
+        .finalize()
+}
+
+fn hash_node<H: Digest>(left: &Output<H>, right: &Output<H>) -> Output<H> {
+    H::new()
+        .chain_update([NODE_PREFIX])
+        .chain_update(left)
+        .chain_update(right)
+        .finalize()
+}
+
+/// Hashes each pair of nodes into the level above. A node without a sibling is promoted unchanged.
+fn parent_level<H: Digest>(level: &[Output<H>]) -> Vec<Output<H>> {
+    level
+        .chunks(2)
+        .map(|pair| match pair {
+            [left, right] => hash_node::<H>(left, right),
+            [single] => single.clone(),
+            _ => unreachable!(),
+        })
+        .collect()
+}
+
+/// Returns the path of the Merkle tree sidecar for `path`, with `.merkle` appended to the file name.
+pub fn sidecar_path(path: impl AsRef<Path>) -> PathBuf {
+    let mut sidecar = OsString::from(path.as_ref());
+    sidecar.push(".merkle");
+    PathBuf::from(sidecar)
+}
+
+/// A Merkle tree over fixed-size chunks of a file, hashed with `H`.
+///
+/// Leaves are the hashes of each chunk and interior nodes the hashes of their two children, with
+/// RFC 6962 domain separation. Ranges of the file can be verified by hashing only the chunks they
+/// touch, and trees of two versions of a file can be compared to find the chunks that differ.
+/// An empty file has a single leaf, the hash of the empty chunk.
+pub struct MerkleTree<H: Digest> {
+    chunk_size: usize,
+    data_len: usize,
+    /// Every level of the tree, from the leaves up to the single root.
+    levels: Vec<Vec<Output<H>>>,
+}
+
+/// The sibling hashes needed to recompute the root from one chunk, returned by `MerkleTree::proof`.
+pub struct MerkleProof<H: Digest> {
+    index: usize,
+    leaf_count: usize,
+    siblings: Vec<Output<H>>,
+}
+
+// Implemented by hand as deriving would require the hasher itself to implement these traits.
+impl<H: Digest> Clone for MerkleTree<H> {
+    fn clone(&self) -> Self {
+        Self {
+            chunk_size: self.chunk_size,
+            data_len: self.data_len,
+            levels: self.levels.clone(),
+        }
+    }
+}
+
+impl<H: Digest> PartialEq for MerkleTree<H> {
+    fn eq(&self, other: &Self) -> bool {
+        self.chunk_size == other.chunk_size
+            && self.data_len == other.data_len
+            && self.root() == other.root()
+    }
+}
+
+impl<H: Digest> Eq for MerkleTree<H> {}
+
+impl<H: Digest> fmt::Debug for MerkleTree<H> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MerkleTree")
+            .field("chunk_size", &self.chunk_size)
+            .field("data_len", &self.data_len)
+            .field("root", self.root())
+            .finish()
+    }
+}
+
+impl<H: Digest> Clone for MerkleProof<H> {
+    fn clone(&self) -> Self {
+        Self {
+            index: self.index,
+            leaf_count: self.leaf_count,
+            siblings: self.siblings.clone(),
+        }
+    }
+}
+
+impl<H: Digest> fmt::Debug for MerkleProof<H> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MerkleProof")
+            .field("index", &self.index)
+            .field("leaf_count", &self.leaf_count)
+            .field("siblings", &self.siblings)
+            .finish()
+    }
+}
+
+impl<H: Digest> MerkleProof<H> {
+    /// Returns the index of the chunk the proof is for.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Returns true if `chunk` is the chunk at the proof's index of the data whose tree has `root`.
+    pub fn verify(&self, root: &Output<H>, chunk: &[u8]) -> bool {
+        let mut hash = hash_leaf::<H>(chunk);
+        let mut siblings = self.siblings.iter();
+        let (mut index, mut width) = (self.index, self.leaf_count);
+        while width > 1 {
+            if index % 2 == 1 {
+                let Some(sibling) = siblings.next() else {
+                    return false;
+                };
+                hash = hash_node::<H>(sibling, &hash);
+            } else if index + 1 < width {
+                let Some(sibling) = siblings.next() else {
+                    return false;
+                };
+                hash = hash_node::<H>(&hash, sibling);
+            }
+            index /= 2;
+            width = width.div_ceil(2);
+        }
+        siblings.next().is_none() && hash == *root
+    }
+}
+
+impl<H: Digest> MerkleTree<H> {
+    /// Builds the tree over the provided bytes split into chunks of `chunk_size` bytes.
+    /// Fails with `InvalidInput` if `chunk_size` is zero.
+    pub fn new(bytes: &[u8], chunk_size: usize) -> io::Result<Self> {
+        if chunk_size == 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "Merkle tree chunk size must not be zero",
+            ));
+        }
+        let leaves = match bytes.is_empty() {
+            true => vec![hash_leaf::<H>(&[])],
+            false => bytes.chunks(chunk_size).map(hash_leaf::<H>).collect(),
+        };
+        Ok(Self::from_leaves(chunk_size, bytes.len(), leaves))
+    }
+
+    fn from_leaves(chunk_size: usize, data_len: usize, leaves: Vec<Output<H>>) -> Self {
+        let mut levels = vec![leaves];
+        while levels[levels.len() - 1].len() > 1 {
+            let parent = parent_level::<H>(&levels[levels.len() - 1]);
+            levels.push(parent);
+        }
+        Self {
+            chunk_size,
+            data_len,
+            levels,
+        }
+    }
+
+    /// Returns the root hash, which covers the whole file.
+    pub fn root(&self) -> &Output<H> {
+        &self.levels[self.levels.len() - 1][0]
+    }
+
+    /// Returns the size of each chunk in bytes. The last chunk may be shorter.
+    pub fn chunk_size(&self) -> usize {
+        self.chunk_size
+    }
+
+    /// Returns the length of the data the tree was built from.
+    pub fn data_len(&self) -> usize {
+        self.data_len
+    }
+
+    /// Returns the number of chunks, which is at least one.
+    pub fn chunk_count(&self) -> usize {
+        self.levels[0].len()
+    }
+
+    /// Returns the hash of the chunk at `index`.
+    pub fn leaf(&self, index: usize) -> Option<&Output<H>> {
+        self.levels[0].get(index)
+    }
+
+    /// Returns the byte range of the chunk at `index` in the file.
+    pub fn chunk_range(&self, index: usize) -> Option<Range<usize>> {
+        (index < self.chunk_count()).then(|| {
+            let start = index * self.chunk_size;
+            start..(start + self.chunk_size).min(self.data_len)
+        })
+    }
+
+    /// Returns the proof that the chunk at `index` belongs to the tree.
+    pub fn proof(&self, index: usize) -> Option<MerkleProof<H>> {
+        self.leaf(index)?;
+        let mut siblings = Vec::new();
+        let mut position = index;
+        for level in &self.levels[..self.levels.len() - 1] {
+            if let Some(sibling) = level.get(position ^ 1) {
+                siblings.push(sibling.clone());
+            }
+            position /= 2;
+        }
+        Some(MerkleProof {
+            index,
+            leaf_count: self.chunk_count(),
+            siblings,
+        })
+    }
+
+    /// Verifies that a byte range of the provided data matches the tree, hashing only the chunks the
+    /// range touches. Returns false if the data's length differs from the tree's.
+    /// Fails with `UnexpectedEof` if the range extends past the end of the tree's data.
+    pub fn verify_range(&self, bytes: &[u8], range: Range<usize>) -> io::Result<bool> {
+        if range.start > range.end || range.end > self.data_len {
+            return Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                format!(
+                    "range {:?} is out of bounds for {} bytes",
+                    range, self.data_len
+                ),
+            ));
+        }
+        if bytes.len() != self.data_len {
+            return Ok(false);
+        }
+        let first = range.start / self.chunk_size;
+        let last = range.end.div_ceil(self.chunk_size).max(first + 1);
+        Ok((first..last.min(self.chunk_count())).all(|index| {
+            let chunk = &bytes[self.chunk_range(index).unwrap_or_default()];
+            hash_leaf::<H>(chunk) == self.levels[0][index]
+        }))
+    }
+
+    /// Returns the indices of the chunks that differ between this tree and `other`, such as the trees
+    /// of two versions of a file. Chunks present in only one tree count as different.
+    /// Trees with the same number of chunks are compared top-down, skipping identical subtrees.
+    /// Fails with `InvalidInput` if the trees use different chunk sizes.
+    pub fn diff_chunks(&self, other: &Self) -> io::Result<Vec<usize>> {
+        if self.chunk_size != other.chunk_size {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "Merkle trees with different chunk sizes can't be compared",
+            ));
+        }
+        if self.chunk_count() != other.chunk_count() {
+            let (shorter, longer) = match self.chunk_count() < other.chunk_count() {
+                true => (self, other),
+                false => (other, self),
+            };
+            let mut diff = (0..shorter.chunk_count())
+                .filter(|index| shorter.levels[0][*index] != longer.levels[0][*index])
+                .collect::<Vec<_>>();
+            diff.extend(shorter.chunk_count()..longer.chunk_count());
+            return Ok(diff);
+        }
+        let mut differing = vec![0];
+        for depth in (0..self.levels.len()).rev() {
+            let (level, other_level) = (&self.levels[depth], &other.levels[depth]);
+            let nodes = differing
+                .into_iter()
+                .filter(|index| level[*index] != other_level[*index]);
+            differing = match depth {
+                0 => nodes.collect(),
+                _ => nodes
+                    .flat_map(|index| [index * 2, index * 2 + 1])
+                    .filter(|index| *index < self.levels[depth - 1].len())
+                    .collect(),
+            };
+        }
+        Ok(differing)
+    }
+
+    /// Writes the tree to a file: a header with the chunk size, data length and hash length, then the leaves.
+    /// Interior nodes are recomputed when the tree is loaded.
+    pub fn save(&self, path: impl AsRef<Path> + Send + Sync) -> io::Result<()> {
+        let hash_len = <H as Digest>::output_size();
+        let mut bytes = Vec::with_capacity(36 + self.chunk_count() * hash_len);
+        bytes.extend_from_slice(&MERKLE_MAGIC);
+        bytes.extend_from_slice(&(self.chunk_size as u64).to_le_bytes());
+        bytes.extend_from_slice(&(self.data_len as u64).to_le_bytes());
+        bytes.extend_from_slice(&(hash_len as u32).to_le_bytes());
+        bytes.extend_from_slice(&(self.chunk_count() as u64).to_le_bytes());
+        for leaf in &self.levels[0] {
+            bytes.extend_from_slice(leaf);
+        }
+        FileWriter::open(path)?.overwrite(bytes)?;
+        Ok(())
+    }
+
+    /// Reads a tree written by `save`.
+    /// Fails with `InvalidData` if the file is not a Merkle tree or was hashed with a different hash length.
+    pub fn load(path: impl AsRef<Path> + Send + Sync) -> io::Result<Self> {
+        let reader = FileReader::open(path)?;
+        let mut cursor = reader.cursor();
+        let invalid = |msg: &str| io::Error::new(io::ErrorKind::InvalidData, msg.to_string());
+        if cursor.read_bytes(MERKLE_MAGIC.len())? != MERKLE_MAGIC {
+            return Err(invalid("not a Merkle tree file"));
+        }
+        let to_usize = |value: u64| {
+            usize::try_from(value)
+                .map_err(|_| invalid("Merkle tree is too large for this platform"))
+        };
+        let chunk_size = to_usize(cursor.read_u64_le()?)?;
+        let data_len = to_usize(cursor.read_u64_le()?)?;
+        let hash_len = cursor.read_u32_le()? as usize;
+        let leaf_count = to_usize(cursor.read_u64_le()?)?;
+        if hash_len != <H as Digest>::output_size() {
+            return Err(invalid(
+                "Merkle tree was built with a different hash length",
+            ));
+        }
+        if chunk_size == 0
+            || leaf_count != data_len.div_ceil(chunk_size).max(1)
+            || leaf_count.checked_mul(hash_len) != Some(cursor.remaining())
+        {
+            return Err(invalid("Merkle tree file is corrupt"));
+        }
+        let leaves = (0..leaf_count)
+            .map(|_| {
+                cursor
+                    .read_bytes(hash_len)
+                    .map(Output::<H>::clone_from_slice)
+            })
+            .collect::<io::Result<Vec<_>>>()?;
+        Ok(Self::from_leaves(chunk_size, data_len, leaves))
+    }
+}
+
+impl<P: AsRef<Path> + Send + Sync> FileReader<P> {
+    /// Builds a Merkle tree over the file data split into chunks of `chunk_size` bytes.
+    /// Fails with `InvalidInput` if `chunk_size` is zero.
+    pub fn merkle_tree<H: Digest>(&self, chunk_size: usize) -> io::Result<MerkleTree<H>> {
+        MerkleTree::new(self.as_slice(), chunk_size)
+    }
+
+    /// Verifies that a byte range of the file matches a Merkle tree, hashing only the chunks it touches.
+    pub fn verify_range<H: Digest>(
+        &self,
+        tree: &MerkleTree<H>,
+        range: Range<usize>,
+    ) -> io::Result<bool> {
+        tree.verify_range(self.as_slice(), range)
+    }
+}
@@ -0,0 +1,92 @@
+use crate::{FileReader, FileWriter};
+use bytemuck::{AnyBitPattern, NoUninit, PodCastError};
+use std::{io, mem::size_of, path::Path};
+
+/// Converts a failed cast into an I/O error describing why the bytes can't be viewed as `T`.
+fn cast_error<T>(error: PodCastError) -> io::Error {
+    let type_name = std::any::type_name::<T>();
+    match error {
+        PodCastError::TargetAlignmentGreaterAndInputNotAligned => io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("data is not aligned for {}", type_name),
+        ),
+        PodCastError::OutputSliceWouldHaveSlop | PodCastError::SizeMismatch => io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!(
+                "data length is not a multiple of the size of {} ({} bytes)",
+                type_name,
+                size_of::<T>()
+            ),
+        ),
+        PodCastError::AlignmentMismatch => {
+            io::Error::new(io::ErrorKind::InvalidData, error.to_string())
+        }
+    }
+}
+
+/// Returns the range of `size_of::<T>()` bytes at `offset`, failing with `UnexpectedEof` if it extends past `len`.
+fn view_range<T>(offset: usize, len: usize) -> io::Result<std::ops::Range<usize>> {
+    offset
+        .checked_add(size_of::<T>())
+        .filter(|end| *end <= len)
+        .map(|end| offset..end)
+        .ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                format!(
+                    "{} at offset {} extends past the end of the data ({} bytes)",
+                    std::any::type_name::<T>(),
+                    offset,
+                    len
+                ),
+            )
+        })
+}
+
+impl<P: AsRef<Path> + Send + Sync> FileReader<P> {
+    /// Returns the file data as a slice of `T`, borrowed directly from the mmap.
+    /// Fails with `InvalidData` if the data is not aligned for `T` or its length is not a multiple of `T`'s size.
+    pub fn as_slice_of<T: AnyBitPattern>(&self) -> io::Result<&[T]> {
+        bytemuck::try_cast_slice(self.as_slice()).map_err(cast_error::<T>)
+    }
+
+    /// Returns a reference to the `T` at `offset` in the file, borrowed directly from the mmap.
+    /// Fails with `UnexpectedEof` if the value extends past the end of the file, and with `InvalidData`
+    /// if `offset` is not aligned for `T`.
+    pub fn view_at<T: AnyBitPattern>(&self, offset: usize) -> io::Result<&T> {
+        let range = view_range::<T>(offset, self.as_slice().len())?;
+        bytemuck::try_from_bytes(&self.as_slice()[range]).map_err(cast_error::<T>)
+    }
+}
+
+impl<P: AsRef<Path> + Send + Sync> FileWriter<P> {
+    /// Returns the file data as a slice of `T`, borrowed directly from the mmap.
+    /// Fails with `InvalidData` if the data is not aligned for `T` or its length is not a multiple of `T`'s size.
+    pub fn as_slice_of<T: AnyBitPattern>(&self) -> io::Result<&[T]> {
+        bytemuck::try_cast_slice(self.bytes()).map_err(cast_error::<T>)
+    }
+
+    /// Returns the file data as a mutable slice of `T`; writes go directly to the mmap.
+    /// Fails as `as_slice_of` does.
+    pub fn as_mut_slice_of<T: AnyBitPattern + NoUninit>(&mut self) -> io::Result<&mut [T]> {
+        bytemuck::try_cast_slice_mut(self.as_slice_mut()).map_err(cast_error::<T>)
+    }
+
+    /// Returns a reference to the `T` at `offset` in the file, borrowed directly from the mmap.
+    /// Fails with `UnexpectedEof` if the value extends past the end of the file, and with `InvalidData`
+    /// if `offset` is not aligned for `T`.
+    pub fn view_at<T: AnyBitPattern>(&self, offset: usize) -> io::Result<&T> {
+        let range = view_range::<T>(offset, self.bytes().len())?;
+        bytemuck::try_from_bytes(&self.bytes()[range]).map_err(cast_error::<T>)
+    }
+
+    /// Returns a mutable reference to the `T` at `offset` in the file; writes go directly to the mmap.
+    /// Fails as `view_at` does.
+    pub fn view_mut_at<T: AnyBitPattern + NoUninit>(
+        &mut self,
+        offset: usize,
+    ) -> io::Result<&mut T> {
+        let range = view_range::<T>(offset, self.bytes().len())?;
+        bytemuck::try_from_bytes_mut(&mut self.as_slice_mut()[range]).map_err(cast_error::<T>)
+    }
+}
@@ -57,8 +57,14 @@ pub(crate) fn rfind_bytes_all<B: AsRef<[u8]>, P: AsRef<[u8]>>(bytes: B, pattern:
     rfind_iter(bytes.as_ref(), pattern.as_ref()).collect::<Vec<usize>>()
 }
 
+#[cfg(feature = "hash")]
+use crate::hash::MultiDigest;
+#[cfg(feature = "hash_encoding")]
+use crate::hash::{parse_hash_str, HashEncoding, NamedDigest};
 #[cfg(feature = "hash")]
 use digest::{Digest, Output};
+#[cfg(feature = "hash")]
+use std::ops::Range;
 
 #[cfg(feature = "hash")]
 pub fn compare_files_with<H: Digest>(
@@ -90,6 +96,25 @@ pub fn compare_files(
     false
 }
 
+#[cfg(feature = "blake3")]
+/// Inputs shorter than this are hashed on the calling thread, as splitting them across threads costs more than it saves.
+const BLAKE3_RAYON_MIN_LEN: usize = 128 * 1024;
+
+#[cfg(feature = "blake3")]
+/// Compares two files by their BLAKE3 hashes, hashing large files on multiple threads.
+/// It takes two file paths `file_path1` and `file_path2`, and returns true if the files are identical (based on their hashes), false otherwise.
+pub fn compare_files_blake3(
+    file_path1: impl AsRef<Path> + Send + Sync,
+    file_path2: impl AsRef<Path> + Send + Sync,
+) -> bool {
+    if let Ok(file1_reader) = FileReader::open(&file_path1) {
+        if let Ok(file2_reader) = FileReader::open(&file_path2) {
+            return file1_reader.blake3() == file2_reader.blake3();
+        }
+    }
+    false
+}
+
 #[cfg(feature = "filepath")]
 use filepath::FilePath;
 #[cfg(feature = "filepath")]
@@ -146,7 +171,10 @@ impl<P: AsRef<Path> + Send + Sync> FileReader<P> {
 
     /// Reads the entire file to a string.
     pub fn read_to_string(&self) -> String {
-        self.as_slice().iter().map(|c| *c as char).collect::<String>()
+        self.as_slice()
+            .iter()
+            .map(|c| *c as char)
+            .collect::<String>()
     }
 
     /// Returns a reference to the FileReader's mmap
@@ -196,7 +224,6 @@ impl<P: AsRef<Path> + Send + Sync> FileReader<P> {
     }
 
     /// Opens the file for writing and returns a FileWriter for it.
-
     pub fn to_writer(self) -> io::Result<FileWriter<P>> {
         FileWriter::open(self.path)
     }
@@ -272,14 +299,59 @@ impl<P: AsRef<Path> + Send + Sync> FileReader<P> {
         H::digest(self.as_slice())
     }
 
+    #[cfg(feature = "hash")]
+    /// Computes the hash of a byte range of the file data using a given hash function.
+    /// Fails with `UnexpectedEof` if the range extends past the end of the file.
+    pub fn hash_range_with<H: Digest>(&self, range: Range<usize>) -> io::Result<Output<H>> {
+        let len = self.as_slice().len();
+        let bytes = self.as_slice().get(range.clone()).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                format!("range {:?} is out of bounds for {} bytes", range, len),
+            )
+        })?;
+        Ok(H::digest(bytes))
+    }
+
+    #[cfg(feature = "hash")]
+    /// Feeds the file data to an existing hasher, so several files can be hashed together
+    /// or combined with other data. Returns the FileReader for chaining.
+    pub fn update_hasher<H: digest::Update>(&self, hasher: &mut H) -> &Self {
+        hasher.update(self.as_slice());
+        self
+    }
+
+    #[cfg(feature = "hash")]
+    /// Computes several hashes of the file data in a single pass over the mapping, such as
+    /// `hash_multi::<(Sha256, Sha3_256, Md5)>()`, returning a tuple of their outputs.
+    pub fn hash_multi<M: MultiDigest>(&self) -> M::Output {
+        let mut hashers = M::new();
+        hashers.update_chunked(self.as_slice());
+        hashers.finalize()
+    }
+
     #[cfg(feature = "hash")]
     /// Computes the hash of the file data using the given hash function and returns it as a hex string.
     pub fn hash_to_string_with<H: Digest>(&self) -> String {
-        let hash = self.hash_with::<H>();
-        hash.iter().fold("".to_string(), |mut acc, byte| {
-            acc.push_str(&format!("{:02x}", byte));
-            acc
-        })
+        crate::hash::to_hex(&self.hash_with::<H>(), false)
+    }
+
+    #[cfg(feature = "hash_encoding")]
+    /// Computes the hash of the file data using the given hash function and returns it in the given encoding.
+    /// Fails with `InvalidInput` if the encoding is `Sri` and Subresource Integrity does not allow the hash function.
+    pub fn hash_to_encoded_string_with<H: NamedDigest>(
+        &self,
+        encoding: HashEncoding,
+    ) -> io::Result<String> {
+        encoding.encode::<H>(&self.hash_with::<H>())
+    }
+
+    #[cfg(feature = "hash_encoding")]
+    /// Compares the hash of the FileReader's file to a hash string in any `HashEncoding`, such as
+    /// hex, base64, a Subresource Integrity string or a multihash.
+    /// Returns false if the string can't be parsed as a hash of the given hash function.
+    pub fn compare_hash_str_with<H: NamedDigest>(&self, hash: &str) -> bool {
+        parse_hash_str::<H>(hash).is_ok_and(|hash| self.compare_hash_with::<H>(&hash))
     }
 
     #[cfg(feature = "hash")]
@@ -316,6 +388,13 @@ impl<P: AsRef<Path> + Send + Sync> FileReader<P> {
         self.compare_hash_with::<Sha3_256>(hash)
     }
 
+    #[cfg(all(feature = "sha3_256", feature = "hash_encoding"))]
+    /// Compares the SHA3-256 hash of the FileReader's file to a hash string in any `HashEncoding`.
+    /// Returns false if the string can't be parsed as a SHA3-256 hash.
+    pub fn compare_hash_str(&self, hash: &str) -> bool {
+        self.compare_hash_str_with::<Sha3_256>(hash)
+    }
+
     #[cfg(feature = "sha3_256")]
     /// Computes the SHA3-256 hash of the file data.
     pub fn hash(&self) -> Output<Sha3_256> {
@@ -328,6 +407,34 @@ impl<P: AsRef<Path> + Send + Sync> FileReader<P> {
         self.hash_to_string_with::<Sha3_256>()
     }
 
+    #[cfg(feature = "blake3")]
+    /// Computes the BLAKE3 hash of the file data.
+    /// Large files are hashed as a tree across the rayon thread pool, which is typically several times
+    /// faster than SHA3-256.
+    pub fn blake3(&self) -> blake3::Hash {
+        let bytes = self.as_slice();
+        let mut hasher = blake3::Hasher::new();
+        if bytes.len() < BLAKE3_RAYON_MIN_LEN {
+            hasher.update(bytes);
+        } else {
+            hasher.update_rayon(bytes);
+        }
+        hasher.finalize()
+    }
+
+    #[cfg(feature = "blake3")]
+    /// Computes the BLAKE3 hash of the file data and returns it as a hex string.
+    pub fn blake3_to_string(&self) -> String {
+        self.blake3().to_hex().to_string()
+    }
+
+    #[cfg(feature = "blake3")]
+    /// Compares the BLAKE3 hash of the FileReader's file to a given hash in constant time.
+    /// It takes a hash `hash`, and returns true if the hash of the file is identical to the given hash, false otherwise.
+    pub fn compare_hash_blake3(&self, hash: &blake3::Hash) -> bool {
+        self.blake3() == *hash
+    }
+
     #[cfg(feature = "sha3_256")]
     /// Compares the FileReader's file to another file by their SHA3-256 hashes.
     /// It takes a file path `file_path`, and returns true if the files are identical (based on their hashes), false otherwise.
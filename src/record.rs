@@ -0,0 +1,256 @@
+use crate::FileWriter;
+use bytemuck::{Pod, Zeroable};
+use std::{
+    io,
+    marker::PhantomData,
+    mem::{align_of, size_of},
+    path::Path,
+};
+
+/// The magic bytes identifying a record file.
+pub const RECORD_MAGIC: [u8; 8] = *b"FRWRECS\0";
+/// The version of the record file layout written by this crate.
+pub const RECORD_VERSION: u32 = 1;
+
+/// Slot state marking a slot that holds a record. Free slots hold the index of the next free slot instead.
+const OCCUPIED: u64 = u64::MAX;
+/// Slot state and free-list head marking the end of the free list.
+const NIL: u64 = u64::MAX - 1;
+/// The fewest slots the file grows by at once.
+const MIN_GROWTH: usize = 16;
+
+/// The header at the start of a record file, stored in native byte order.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+struct Header {
+    magic: [u8; 8],
+    version: u32,
+    record_size: u32,
+    /// Number of records currently stored.
+    count: u64,
+    /// Number of slots ever used; slots past this are unused capacity.
+    slots: u64,
+    /// Index of the first free slot, or `NIL`.
+    free_head: u64,
+}
+
+const HEADER_LEN: usize = size_of::<Header>();
+
+/// A file of fixed-size `T` records with slot reuse, built on a `FileWriter`.
+///
+/// The file starts with a header (magic, version, record size, record count and free-list head),
+/// followed by slots each holding an 8-byte state and a record padded to 8 bytes. Removed slots are
+/// chained into a free list and reused by `push`, so record indices are stable for as long as the
+/// record exists. The file grows via `extend_len_by` in chunks that double with its capacity.
+/// Records are stored in native byte order and must not need more than 8-byte alignment.
+pub struct RecordFile<T: Pod, P: AsRef<Path> + Send + Sync> {
+    writer: FileWriter<P>,
+    header: Header,
+    record: PhantomData<T>,
+}
+
+impl<T: Pod, P: AsRef<Path> + Send + Sync> RecordFile<T, P> {
+    /// Distance between the starts of consecutive slots.
+    const STRIDE: usize = 8 + size_of::<T>().div_ceil(8) * 8;
+
+    /// Opens a record file of `T`, writing a new header if the file is empty.
+    /// Fails with `InvalidData` if the file has a different magic, version or record size,
+    /// and with `InvalidInput` if `T` needs more than 8-byte alignment.
+    pub fn open(mut writer: FileWriter<P>) -> io::Result<Self> {
+        if align_of::<T>() > 8 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "{} needs {}-byte alignment, record files support at most 8",
+                    std::any::type_name::<T>(),
+                    align_of::<T>()
+                ),
+            ));
+        }
+        let record_size = u32::try_from(size_of::<T>())
+            .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "record type is too large"))?;
+        if writer.is_empty() {
+            let header = Header {
+                magic: RECORD_MAGIC,
+                version: RECORD_VERSION,
+                record_size,
+                count: 0,
+                slots: 0,
+                free_head: NIL,
+            };
+            writer.overwrite(bytemuck::bytes_of(&header))?;
+            return Ok(Self {
+                writer,
+                header,
+                record: PhantomData,
+            });
+        }
+        let header = *writer.view_at::<Header>(0)?;
+        let invalid = |msg: String| Err(io::Error::new(io::ErrorKind::InvalidData, msg));
+        if header.magic != RECORD_MAGIC {
+            return invalid("not a record file".to_string());
+        }
+        if header.version != RECORD_VERSION {
+            return invalid(format!(
+                "unsupported record file version {}",
+                header.version
+            ));
+        }
+        if header.record_size != record_size {
+            return invalid(format!(
+                "record file holds {}-byte records, not {}",
+                header.record_size, record_size
+            ));
+        }
+        let record_file = Self {
+            writer,
+            header,
+            record: PhantomData,
+        };
+        if header.slots > record_file.capacity() as u64 || header.count > header.slots {
+            return invalid("record file header does not match its length".to_string());
+        }
+        if header.free_head != NIL && header.free_head >= header.slots {
+            return invalid(format!(
+                "record file free list starts at slot {} of {}",
+                header.free_head, header.slots
+            ));
+        }
+        Ok(record_file)
+    }
+
+    /// Returns the number of records stored.
+    pub fn len(&self) -> usize {
+        self.header.count as usize
+    }
+
+    /// Returns true if no records are stored.
+    pub fn is_empty(&self) -> bool {
+        self.header.count == 0
+    }
+
+    /// Returns the number of slots the file has room for without growing.
+    pub fn capacity(&self) -> usize {
+        self.writer.len().saturating_sub(HEADER_LEN) / Self::STRIDE
+    }
+
+    /// Returns the offset of the slot with the provided index.
+    fn slot_offset(index: usize) -> usize {
+        HEADER_LEN + index * Self::STRIDE
+    }
+
+    /// Returns the state of the slot with the provided index, or `None` if it has never been used.
+    fn slot_state(&self, index: usize) -> Option<u64> {
+        if index as u64 >= self.header.slots {
+            return None;
+        }
+        self.writer
+            .view_at::<u64>(Self::slot_offset(index))
+            .ok()
+            .copied()
+    }
+
+    fn set_slot_state(&mut self, index: usize, state: u64) {
+        self.writer
+            .write_to_offset(state.to_ne_bytes(), Self::slot_offset(index));
+    }
+
+    fn write_header(&mut self) {
+        let header = self.header;
+        self.writer.write_to_offset(bytemuck::bytes_of(&header), 0);
+    }
+
+    /// Returns the record at `index`, or `None` if there is no record there.
+    pub fn get(&self, index: usize) -> Option<&T> {
+        (self.slot_state(index)? == OCCUPIED)
+            .then(|| self.writer.view_at::<T>(Self::slot_offset(index) + 8).ok())?
+    }
+
+    /// Returns a mutable reference to the record at `index`, or `None` if there is no record there.
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        if self.slot_state(index)? != OCCUPIED {
+            return None;
+        }
+        self.writer
+            .view_mut_at::<T>(Self::slot_offset(index) + 8)
+            .ok()
+    }
+
+    /// Stores `record` in the first free slot, growing the file if there is none, and returns its index.
+    /// Fails with `InvalidData` if the free list points at a slot that is not free.
+    pub fn push(&mut self, record: &T) -> io::Result<usize> {
+        let index = match self.header.free_head {
+            NIL => {
+                let index = self.header.slots as usize;
+                if index == self.capacity() {
+                    let growth = self.capacity().max(MIN_GROWTH);
+                    self.writer.extend_len_by(growth * Self::STRIDE)?;
+                }
+                self.header.slots += 1;
+                index
+            }
+            free_head => {
+                let index = free_head as usize;
+                let next = self
+                    .slot_state(index)
+                    .filter(|next| *next == NIL || *next < self.header.slots)
+                    .ok_or_else(|| {
+                        io::Error::new(
+                            io::ErrorKind::InvalidData,
+                            format!(
+                                "record file free list points at slot {}, which is not free",
+                                index
+                            ),
+                        )
+                    })?;
+                self.header.free_head = next;
+                index
+            }
+        };
+        self.set_slot_state(index, OCCUPIED);
+        self.writer
+            .write_to_offset(bytemuck::bytes_of(record), Self::slot_offset(index) + 8);
+        self.header.count += 1;
+        self.write_header();
+        Ok(index)
+    }
+
+    /// Replaces the record at `index`, returning false if there is no record there.
+    pub fn update(&mut self, index: usize, record: &T) -> bool {
+        match self.get_mut(index) {
+            Some(slot) => {
+                *slot = *record;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Removes the record at `index`, returning it, and adds its slot to the free list.
+    pub fn remove(&mut self, index: usize) -> Option<T> {
+        let record = *self.get(index)?;
+        let free_head = self.header.free_head;
+        self.set_slot_state(index, free_head);
+        self.writer
+            .write_to_offset(vec![0; Self::STRIDE - 8], Self::slot_offset(index) + 8);
+        self.header.free_head = index as u64;
+        self.header.count -= 1;
+        self.write_header();
+        Some(record)
+    }
+
+    /// Returns an iterator over the stored records and their indices, in index order.
+    pub fn iter(&self) -> impl Iterator<Item = (usize, &T)> + '_ {
+        (0..self.header.slots as usize).filter_map(move |index| Some((index, self.get(index)?)))
+    }
+
+    /// Returns the underlying `FileWriter`.
+    pub fn writer(&self) -> &FileWriter<P> {
+        &self.writer
+    }
+
+    /// Consumes the record file, returning the underlying `FileWriter`.
+    pub fn into_writer(self) -> FileWriter<P> {
+        self.writer
+    }
+}
@@ -0,0 +1,255 @@
+use crate::{lines::newline_offsets, FileReader, FileWriter};
+use serde::{Deserialize, Serialize};
+use std::{fmt, io, marker::PhantomData, path::Path};
+
+/// Converts a (de)serialization error into an `InvalidData` I/O error.
+fn invalid_data<E: Into<Box<dyn std::error::Error + Send + Sync>>>(error: E) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, error)
+}
+
+/// A writer that discards its input, counting the bytes written to it.
+#[derive(Default)]
+struct CountingWriter {
+    len: usize,
+}
+
+impl io::Write for CountingWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.len += buf.len();
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// An error deserializing a record of a JSON Lines file, with the record's location.
+#[derive(Debug)]
+pub struct JsonLineError {
+    line: usize,
+    offset: usize,
+    source: serde_json::Error,
+}
+
+impl JsonLineError {
+    /// Returns the one-based line number of the record.
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    /// Returns the byte offset of the start of the record's line in the file.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Returns the underlying deserialization error.
+    pub fn source_error(&self) -> &serde_json::Error {
+        &self.source
+    }
+}
+
+impl fmt::Display for JsonLineError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {} (byte offset {}): {}",
+            self.line, self.offset, self.source
+        )
+    }
+}
+
+impl std::error::Error for JsonLineError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.source)
+    }
+}
+
+impl From<JsonLineError> for io::Error {
+    fn from(error: JsonLineError) -> Self {
+        io::Error::new(io::ErrorKind::InvalidData, error)
+    }
+}
+
+/// Deserializes one record of a JSON Lines file.
+fn parse_json_line<'a, T: Deserialize<'a>>(
+    bytes: &'a [u8],
+    line: usize,
+    offset: usize,
+) -> Result<T, JsonLineError> {
+    serde_json::from_slice(bytes).map_err(|source| JsonLineError {
+        line,
+        offset,
+        source,
+    })
+}
+
+/// A lazy iterator over the records of a JSON Lines (NDJSON) file, returned by `FileReader::json_lines`.
+/// Blank lines are skipped.
+pub struct JsonLines<'a, T> {
+    bytes: &'a [u8],
+    offset: usize,
+    line: usize,
+    record: PhantomData<fn() -> T>,
+}
+
+impl<'a, T: Deserialize<'a>> Iterator for JsonLines<'a, T> {
+    type Item = Result<T, JsonLineError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.offset < self.bytes.len() {
+            let start = self.offset;
+            let rest = &self.bytes[start..];
+            let len = newline_offsets(rest).next().unwrap_or(rest.len());
+            self.offset = start + len + 1;
+            self.line += 1;
+            let record = &rest[..len];
+            if !record.trim_ascii().is_empty() {
+                return Some(parse_json_line(record, self.line, start));
+            }
+        }
+        None
+    }
+}
+
+impl<P: AsRef<Path> + Send + Sync> FileReader<P> {
+    /// Returns a lazy iterator deserializing each line of the file as a JSON record.
+    /// Blank lines are skipped. Errors report the line number and byte offset of the failing record,
+    /// and iteration can continue past them. Records may borrow `&'a str` from the mmap.
+    pub fn json_lines<'a, T: Deserialize<'a>>(&'a self) -> JsonLines<'a, T> {
+        JsonLines {
+            bytes: self.as_slice(),
+            offset: 0,
+            line: 0,
+            record: PhantomData,
+        }
+    }
+
+    #[cfg(feature = "rayon")]
+    /// Deserializes every line of the file as a JSON record in parallel, returning the results in file order.
+    /// Blank lines are skipped. Behaves as `json_lines` otherwise.
+    pub fn par_json_lines<'a, T: Deserialize<'a> + Send>(
+        &'a self,
+    ) -> Vec<Result<T, JsonLineError>> {
+        use rayon::prelude::*;
+        let bytes = self.as_slice();
+        let index = crate::lines::LineIndex::new(bytes);
+        (0..index.len())
+            .into_par_iter()
+            .filter_map(|n| {
+                let range = index.line(n).unwrap_or_default();
+                let record = &bytes[range.clone()];
+                (!record.trim_ascii().is_empty())
+                    .then(|| parse_json_line(record, n + 1, range.start))
+            })
+            .collect()
+    }
+
+    /// Deserializes the file data as JSON.
+    /// Strings without escapes can be borrowed from the mmap as `&'a str`, avoiding copies.
+    pub fn deserialize_json<'a, T: Deserialize<'a>>(&'a self) -> io::Result<T> {
+        serde_json::from_slice(self.as_slice()).map_err(invalid_data)
+    }
+
+    #[cfg(feature = "toml")]
+    /// Deserializes the file data as TOML.
+    pub fn deserialize_toml<T: serde::de::DeserializeOwned>(&self) -> io::Result<T> {
+        let text = std::str::from_utf8(self.as_slice()).map_err(invalid_data)?;
+        toml::from_str(text).map_err(invalid_data)
+    }
+
+    #[cfg(feature = "bincode")]
+    /// Deserializes the file data as bincode.
+    /// Borrowed `&'a str` and `&'a [u8]` fields point directly into the mmap.
+    pub fn deserialize_bincode<'a, T: Deserialize<'a>>(&'a self) -> io::Result<T> {
+        bincode::deserialize(self.as_slice()).map_err(invalid_data)
+    }
+
+    #[cfg(feature = "postcard")]
+    /// Deserializes the file data as postcard.
+    /// Borrowed `&'a str` and `&'a [u8]` fields point directly into the mmap.
+    pub fn deserialize_postcard<'a, T: Deserialize<'a>>(&'a self) -> io::Result<T> {
+        postcard::from_bytes(self.as_slice()).map_err(invalid_data)
+    }
+}
+
+impl<P: AsRef<Path> + Send + Sync> FileWriter<P> {
+    /// Sets the file's length to `len` and passes the mmap to `write`, which must fill it exactly.
+    fn overwrite_with<F: FnOnce(&mut [u8]) -> io::Result<()>>(
+        &mut self,
+        len: usize,
+        write: F,
+    ) -> io::Result<&Self> {
+        self.set_len(len)?;
+        write(self.as_slice_mut())?;
+        Ok(self)
+    }
+
+    /// Overwrites the file with the JSON serialization of `value`, pretty-printed if `pretty` is true.
+    /// The serialized length is computed first so the file is sized exactly once, then the value is
+    /// serialized directly into the mmap without an intermediate buffer.
+    pub fn serialize_json<T: Serialize + ?Sized>(
+        &mut self,
+        value: &T,
+        pretty: bool,
+    ) -> io::Result<&Self> {
+        let to_writer = |writer: &mut dyn io::Write| match pretty {
+            true => serde_json::to_writer_pretty(writer, value),
+            false => serde_json::to_writer(writer, value),
+        };
+        let mut counter = CountingWriter::default();
+        to_writer(&mut counter).map_err(invalid_data)?;
+        self.overwrite_with(counter.len, |mut mmap| {
+            to_writer(&mut mmap).map_err(invalid_data)
+        })
+    }
+
+    /// Appends the JSON serialization of `value` to the file as a single JSON Lines record, followed by a newline.
+    /// If the file is not empty and does not end with a newline, one is added before the record.
+    pub fn append_json_line<T: Serialize + ?Sized>(&mut self, value: &T) -> io::Result<&Self> {
+        let mut record = Vec::new();
+        if !self.is_empty() && !self.bytes().ends_with(b"\n") {
+            record.push(b'\n');
+        }
+        serde_json::to_writer(&mut record, value).map_err(invalid_data)?;
+        record.push(b'\n');
+        self.append(record)
+    }
+
+    #[cfg(feature = "toml")]
+    /// Overwrites the file with the TOML serialization of `value`, pretty-printed if `pretty` is true.
+    pub fn serialize_toml<T: Serialize + ?Sized>(
+        &mut self,
+        value: &T,
+        pretty: bool,
+    ) -> io::Result<&Self> {
+        let text = match pretty {
+            true => toml::to_string_pretty(value),
+            false => toml::to_string(value),
+        }
+        .map_err(invalid_data)?;
+        self.overwrite(text)
+    }
+
+    #[cfg(feature = "bincode")]
+    /// Overwrites the file with the bincode serialization of `value`.
+    /// The file is sized exactly once and the value is serialized directly into the mmap.
+    pub fn serialize_bincode<T: Serialize + ?Sized>(&mut self, value: &T) -> io::Result<&Self> {
+        let len = bincode::serialized_size(value).map_err(invalid_data)?;
+        self.overwrite_with(len as usize, |mmap| {
+            bincode::serialize_into(mmap, value).map_err(invalid_data)
+        })
+    }
+
+    #[cfg(feature = "postcard")]
+    /// Overwrites the file with the postcard serialization of `value`.
+    /// The file is sized exactly once and the value is serialized directly into the mmap.
+    pub fn serialize_postcard<T: Serialize + ?Sized>(&mut self, value: &T) -> io::Result<&Self> {
+        let len = postcard::experimental::serialized_size(value).map_err(invalid_data)?;
+        self.overwrite_with(len, |mmap| {
+            postcard::to_slice(value, mmap)
+                .map(|_| ())
+                .map_err(invalid_data)
+        })
+    }
+}
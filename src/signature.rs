@@ -0,0 +1,130 @@
+use crate::{
+    utils::{get_mmap_read, overwrite},
+    FileReader,
+};
+use ed25519_dalek::{Digest, Sha512, Signature, SigningKey, VerifyingKey, SIGNATURE_LENGTH};
+use std::{
+    ffi::OsString,
+    io,
+    path::{Path, PathBuf},
+};
+
+/// Bytes hashed at a time when prehashing, so huge files are hashed in cache-sized pieces.
+const PREHASH_CHUNK_LEN: usize = 1024 * 1024;
+
+/// How a file is signed.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SignatureMode<'a> {
+    /// PureEd25519 over the file data, which reads the data twice while signing.
+    #[default]
+    Pure,
+    /// Ed25519ph over the SHA-512 of the file data, which reads the data once and suits huge files.
+    /// The optional context, of at most 255 bytes, separates signatures made for different purposes.
+    Prehashed(Option<&'a [u8]>),
+}
+
+fn invalid_signature(error: impl std::fmt::Display) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, error.to_string())
+}
+
+/// Returns the path of the detached signature for `path`, with `.sig` appended to the file name.
+pub fn signature_path(path: impl AsRef<Path>) -> PathBuf {
+    let mut sig_path = OsString::from(path.as_ref());
+    sig_path.push(".sig");
+    PathBuf::from(sig_path)
+}
+
+/// Writes a detached signature file containing the 64 raw signature bytes.
+pub fn write_signature(
+    signature: &Signature,
+    sig_path: impl AsRef<Path> + Send + Sync,
+) -> io::Result<()> {
+    overwrite(sig_path, signature.to_bytes())
+}
+
+/// Reads a detached signature file containing either the 64 raw signature bytes or their 128 hex digits.
+/// Fails with `InvalidData` if the file holds neither.
+pub fn read_signature(sig_path: impl AsRef<Path> + Send + Sync) -> io::Result<Signature> {
+    let mmap = get_mmap_read(sig_path)?;
+    let bytes = match mmap.len() {
+        SIGNATURE_LENGTH => mmap.to_vec(),
+        _ => {
+            let hex = std::str::from_utf8(&mmap)
+                .map_err(invalid_signature)?
+                .trim();
+            (0..hex.len())
+                .step_by(2)
+                .map(|i| {
+                    hex.get(i..i + 2)
+                        .and_then(|pair| u8::from_str_radix(pair, 16).ok())
+                })
+                .collect::<Option<Vec<u8>>>()
+                .ok_or_else(|| invalid_signature("signature file is not raw bytes or hex"))?
+        }
+    };
+    Signature::from_slice(&bytes).map_err(invalid_signature)
+}
+
+impl<P: AsRef<Path> + Send + Sync> FileReader<P> {
+    /// Computes the SHA-512 of the file data for Ed25519ph.
+    fn prehash(&self) -> Sha512 {
+        let mut hasher = Sha512::new();
+        for chunk in self.as_slice().chunks(PREHASH_CHUNK_LEN) {
+            hasher.update(chunk);
+        }
+        hasher
+    }
+
+    /// Signs the file data with PureEd25519.
+    pub fn sign(&self, key: &SigningKey) -> Signature {
+        use ed25519_dalek::Signer;
+        key.sign(self.as_slice())
+    }
+
+    /// Verifies a PureEd25519 signature of the file data, rejecting weak keys and malleable signatures.
+    pub fn verify(&self, key: &VerifyingKey, signature: &Signature) -> bool {
+        key.verify_strict(self.as_slice(), signature).is_ok()
+    }
+
+    /// Signs the file data in the provided mode.
+    /// Fails with `InvalidInput` if a prehash context is longer than 255 bytes.
+    pub fn sign_with(&self, key: &SigningKey, mode: SignatureMode) -> io::Result<Signature> {
+        match mode {
+            SignatureMode::Pure => Ok(self.sign(key)),
+            SignatureMode::Prehashed(context) => key
+                .sign_prehashed(self.prehash(), context)
+                .map_err(|error| io::Error::new(io::ErrorKind::InvalidInput, error.to_string())),
+        }
+    }
+
+    /// Verifies a signature of the file data made in the provided mode.
+    pub fn verify_with(
+        &self,
+        key: &VerifyingKey,
+        signature: &Signature,
+        mode: SignatureMode,
+    ) -> bool {
+        match mode {
+            SignatureMode::Pure => self.verify(key, signature),
+            SignatureMode::Prehashed(context) => key
+                .verify_prehashed_strict(self.prehash(), context, signature)
+                .is_ok(),
+        }
+    }
+
+    /// Signs the file data in the provided mode and writes the signature to a detached `.sig` file
+    /// next to the file, returning the signature file's path.
+    pub fn sign_detached(&self, key: &SigningKey, mode: SignatureMode) -> io::Result<PathBuf> {
+        let signature = self.sign_with(key, mode)?;
+        let sig_path = signature_path(&self.path);
+        write_signature(&signature, &sig_path)?;
+        Ok(sig_path)
+    }
+
+    /// Verifies the file data against the detached `.sig` file next to it, made in the provided mode.
+    /// Fails if the signature file can't be read.
+    pub fn verify_detached(&self, key: &VerifyingKey, mode: SignatureMode) -> io::Result<bool> {
+        let signature = read_signature(signature_path(&self.path))?;
+        Ok(self.verify_with(key, &signature, mode))
+    }
+}
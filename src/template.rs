@@ -0,0 +1,177 @@
+use crate::FileWriter;
+use std::{borrow::Borrow, collections::HashMap, hash::Hash, io, ops::Range, path::Path};
+
+/// The delimiters that surround placeholder names in a template, `{{` and `}}` by default.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Delimiters<'a> {
+    pub open: &'a [u8],
+    pub close: &'a [u8],
+}
+
+impl Default for Delimiters<'_> {
+    fn default() -> Self {
+        Self {
+            open: b"{{",
+            close: b"}}",
+        }
+    }
+}
+
+/// What to do with a placeholder whose name is not in the map of values.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum OnMissing {
+    /// Fail with a `NotFound` error, leaving the file unchanged.
+    #[default]
+    Error,
+    /// Leave the placeholder in place, delimiters included.
+    Leave,
+    /// Replace the placeholder with nothing.
+    Empty,
+}
+
+/// How substituted values are escaped.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Escape {
+    /// Values are substituted verbatim.
+    #[default]
+    None,
+    /// `&`, `<`, `>`, `"` and `'` are replaced with HTML entities.
+    Html,
+    /// Values are escaped for use inside a JSON string literal.
+    Json,
+    /// Values are wrapped in single quotes for POSIX shells, escaping any single quotes within.
+    Shell,
+}
+
+impl Escape {
+    /// Appends the escaped value to the output.
+    fn push_escaped(&self, output: &mut Vec<u8>, value: &str) {
+        match self {
+            Escape::None => output.extend_from_slice(value.as_bytes()),
+            Escape::Html => {
+                for byte in value.bytes() {
+                    match byte {
+                        b'&' => output.extend_from_slice(b"&amp;"),
+                        b'<' => output.extend_from_slice(b"&lt;"),
+                        b'>' => output.extend_from_slice(b"&gt;"),
+                        b'"' => output.extend_from_slice(b"&quot;"),
+                        b'\'' => output.extend_from_slice(b"&#39;"),
+                        byte => output.push(byte),
+                    }
+                }
+            }
+            Escape::Json => {
+                for c in value.chars() {
+                    match c {
+                        '"' => output.extend_from_slice(b"\\\""),
+                        '\\' => output.extend_from_slice(b"\\\\"),
+                        '\n' => output.extend_from_slice(b"\\n"),
+                        '\r' => output.extend_from_slice(b"\\r"),
+                        '\t' => output.extend_from_slice(b"\\t"),
+                        c if (c as u32) < 0x20 => {
+                            output.extend_from_slice(format!("\\u{:04x}", c as u32).as_bytes())
+                        }
+                        c => output.extend_from_slice(c.encode_utf8(&mut [0; 4]).as_bytes()),
+                    }
+                }
+            }
+            Escape::Shell => {
+                output.push(b'\'');
+                output.extend_from_slice(value.replace('\'', "'\\''").as_bytes());
+                output.push(b'\'');
+            }
+        }
+    }
+}
+
+/// Finds every placeholder in the template and computes its substitution.
+/// Placeholder names are trimmed of surrounding whitespace, so `{{ name }}` and `{{name}}` are equivalent.
+/// An opening delimiter without a closing delimiter after it is left as-is.
+/// Fails with `InvalidInput` if either delimiter is empty.
+pub(crate) fn render_edits<K, V>(
+    template: &[u8],
+    values: &HashMap<K, V>,
+    delimiters: Delimiters,
+    on_missing: OnMissing,
+    escape: Escape,
+) -> io::Result<Vec<(Range<usize>, Vec<u8>)>>
+where
+    K: Borrow<str> + Hash + Eq,
+    V: AsRef<str>,
+{
+    if delimiters.open.is_empty() || delimiters.close.is_empty() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "template delimiters must not be empty",
+        ));
+    }
+    let mut edits = Vec::new();
+    let mut cursor = 0;
+    while let Some(open) = crate::read::find_bytes(&template[cursor..], delimiters.open) {
+        let open = cursor + open;
+        let name_start = open + delimiters.open.len();
+        let Some(name_len) = crate::read::find_bytes(&template[name_start..], delimiters.close)
+        else {
+            break;
+        };
+        let end = name_start + name_len + delimiters.close.len();
+        let name = std::str::from_utf8(&template[name_start..name_start + name_len])
+            .map(str::trim)
+            .unwrap_or_default();
+        match (values.get(name), on_missing) {
+            (Some(value), _) => {
+                let mut output = Vec::new();
+                escape.push_escaped(&mut output, value.as_ref());
+                edits.push((open..end, output));
+            }
+            (None, OnMissing::Error) => {
+                return Err(io::Error::new(
+                    io::ErrorKind::NotFound,
+                    format!("no value for template placeholder {:?}", name),
+                ))
+            }
+            (None, OnMissing::Leave) => {}
+            (None, OnMissing::Empty) => edits.push((open..end, Vec::new())),
+        }
+        cursor = end;
+    }
+    Ok(edits)
+}
+
+impl<P: AsRef<Path> + Send + Sync> FileWriter<P> {
+    /// Replaces every placeholder in the file with its value from `values`, verbatim.
+    /// Placeholders are names surrounded by `delimiters`, with surrounding whitespace ignored, as in `{{ name }}`.
+    /// Unknown names are handled according to `on_missing`; on error the file is left unchanged.
+    /// Fails with `InvalidInput` if either delimiter is empty.
+    /// The file is resized at most once. Returns the number of placeholders replaced.
+    pub fn render_placeholders<K, V>(
+        &mut self,
+        values: &HashMap<K, V>,
+        delimiters: Delimiters,
+        on_missing: OnMissing,
+    ) -> io::Result<usize>
+    where
+        K: Borrow<str> + Hash + Eq,
+        V: AsRef<str>,
+    {
+        self.render_placeholders_escaped(values, delimiters, on_missing, Escape::None)
+    }
+
+    /// Replaces every placeholder in the file with its value from `values`, escaped according to `escape`.
+    /// Behaves as `render_placeholders` otherwise.
+    pub fn render_placeholders_escaped<K, V>(
+        &mut self,
+        values: &HashMap<K, V>,
+        delimiters: Delimiters,
+        on_missing: OnMissing,
+        escape: Escape,
+    ) -> io::Result<usize>
+    where
+        K: Borrow<str> + Hash + Eq,
+        V: AsRef<str>,
+    {
+        let edits = render_edits(self.bytes(), values, delimiters, on_missing, escape)?;
+        self.splice_ranges(&edits)?;
+        Ok(edits.len())
+    }
+}
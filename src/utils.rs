@@ -83,3 +83,100 @@ pub fn append(
     mmap[file_len as usize..].copy_from_slice(slice);
     Ok(())
 }
+
+#[cfg(feature = "search")]
+/// Renders the template file at `src_path` into the file at `dst_path`, replacing every `{{ name }}`
+/// placeholder with its value from `values`. The destination file is created if needed and resized once.
+/// Fails with `NotFound`, without writing the destination, if a placeholder has no value.
+/// Use `FileWriter::render_placeholders` for other delimiters or to leave unknown placeholders in place.
+pub fn render_template<K, V>(
+    src_path: impl AsRef<Path> + Send + Sync,
+    dst_path: impl AsRef<Path> + Send + Sync,
+    values: &std::collections::HashMap<K, V>,
+) -> std::io::Result<()>
+where
+    K: std::borrow::Borrow<str> + std::hash::Hash + Eq,
+    V: AsRef<str>,
+{
+    use crate::template::{render_edits, Delimiters, Escape, OnMissing};
+    let template = get_mmap_read(src_path)?;
+    let edits = render_edits(
+        &template,
+        values,
+        Delimiters::default(),
+        OnMissing::Error,
+        Escape::None,
+    )?;
+    let mut output = Vec::with_capacity(template.len());
+    let mut cursor = 0;
+    for (range, bytes) in &edits {
+        output.extend_from_slice(&template[cursor..range.start]);
+        output.extend_from_slice(bytes);
+        cursor = range.end;
+    }
+    output.extend_from_slice(&template[cursor..]);
+    overwrite(dst_path, output)
+}
+
+#[cfg(feature = "manifest")]
+/// Writes a GNU coreutils style checksum manifest, as `sha256sum` or `b2sum` would, listing the digest
+/// of every file in `paths` with the provided algorithm.
+/// Paths are written as given, so relative paths should be relative to the manifest's directory.
+pub fn write_manifest<I, Q>(
+    paths: I,
+    algorithm: crate::manifest::ManifestAlgorithm,
+    manifest_path: impl AsRef<Path> + Send + Sync,
+) -> std::io::Result<()>
+where
+    I: IntoIterator<Item = Q>,
+    Q: AsRef<Path> + Send + Sync,
+{
+    write_manifest_with(
+        paths,
+        algorithm,
+        crate::manifest::ManifestStyle::Gnu,
+        manifest_path,
+    )
+}
+
+#[cfg(feature = "manifest")]
+/// Writes a checksum manifest in the provided style, listing the digest of every file in `paths`
+/// with the provided algorithm. Behaves as `write_manifest` otherwise.
+pub fn write_manifest_with<I, Q>(
+    paths: I,
+    algorithm: crate::manifest::ManifestAlgorithm,
+    style: crate::manifest::ManifestStyle,
+    manifest_path: impl AsRef<Path> + Send + Sync,
+) -> std::io::Result<()>
+where
+    I: IntoIterator<Item = Q>,
+    Q: AsRef<Path> + Send + Sync,
+{
+    let mut manifest = String::new();
+    for path in paths {
+        let reader = crate::FileReader::open(&path)?;
+        let hex = algorithm.hash_to_string(&reader);
+        manifest.push_str(&crate::manifest::format_line(
+            algorithm,
+            style,
+            &hex,
+            path.as_ref(),
+        ));
+        manifest.push('\n');
+    }
+    overwrite(manifest_path, manifest)
+}
+
+#[cfg(feature = "manifest")]
+/// Verifies every file listed in a checksum manifest in GNU coreutils (`sha256sum`, `b2sum`, with or
+/// without the `*` binary marker) or BSD (`SHA256 (file) = ...`) style, returning a report of each file.
+/// Relative paths are resolved against the manifest's directory. Blank lines and `#` comments are skipped.
+/// BSD lines name their algorithm; for GNU lines it is guessed from the manifest's file name
+/// (`SHA512SUMS`, `*.b2`, ...), falling back to SHA-256 or SHA-512 by digest length.
+/// Fails with `InvalidData` if a line can't be parsed.
+pub fn verify_manifest(
+    manifest_path: impl AsRef<Path> + Send + Sync,
+) -> std::io::Result<Vec<crate::manifest::ManifestEntry>> {
+    let manifest = read_to_string(&manifest_path)?;
+    crate::manifest::verify(&manifest, manifest_path.as_ref())
+}
@@ -1,6 +1,8 @@
 use crate::{file::open_as_write, FileReader};
 use memmap2::{Mmap, MmapMut};
-use std::{fmt, fs::File, io, path::Path};
+#[cfg(feature = "search")]
+use std::borrow::Cow;
+use std::{fmt, fs::File, io, ops::Range, path::Path};
 
 /// `FileWriter` is a structure that allows writing to a file.
 /// It uses memory-mapped files for efficient file manipulation.
@@ -116,6 +118,33 @@ impl<P: AsRef<Path> + Send + Sync> FileWriter<P> {
         self
     }
 
+    /// Replaces each of the provided ranges of the file with its paired bytes, which may differ in length from the range.
+    /// The ranges must be sorted and must not overlap. The file is resized at most once, and only the
+    /// bytes from the first edited range onwards are rewritten.
+    pub(crate) fn splice_ranges<B: AsRef<[u8]>>(
+        &mut self,
+        edits: &[(Range<usize>, B)],
+    ) -> io::Result<()> {
+        let first = match edits.first() {
+            Some((range, _)) => range.start,
+            None => return Ok(()),
+        };
+        let mut tail = Vec::with_capacity(self.len() - first);
+        let mut cursor = first;
+        for (range, bytes) in edits {
+            tail.extend_from_slice(&self.mmap[cursor..range.start]);
+            tail.extend_from_slice(bytes.as_ref());
+            cursor = range.end;
+        }
+        tail.extend_from_slice(&self.mmap[cursor..]);
+        let new_len = first + tail.len();
+        if new_len != self.len() {
+            self.set_len(new_len)?;
+        }
+        self.mmap[first..].copy_from_slice(&tail);
+        Ok(())
+    }
+
     #[cfg(feature = "search")]
     /// Finds a sequence of bytes in the file and replaces it with another sequence of bytes. If the sequence to find is not found, it does nothing.
     /// If the sequence would be written past the length of the file, the file is extended to accommodate the new bytes.
@@ -208,6 +237,149 @@ impl<P: AsRef<Path> + Send + Sync> FileWriter<P> {
         Ok(self)
     }
 
+    #[cfg(feature = "search")]
+    /// Replaces every occurrence of each `(find, replace)` pair in a single pass over the file.
+    /// Substitution is simultaneous - replaced bytes are never searched again, so one pair's
+    /// replacement cannot be matched by another pair. Where matches of several pairs start at the
+    /// same offset, the longest `find` wins, then the pair listed first.
+    /// The file is resized at most once. Returns the number of replacements made for each pair, in order.
+    /// Fails with `InvalidInput` if any `find` is empty.
+    pub fn replace_many<B: AsRef<[u8]>, BO: AsRef<[u8]>>(
+        &mut self,
+        pairs: &[(B, BO)],
+    ) -> io::Result<Vec<usize>> {
+        if pairs.iter().any(|(find, _)| find.as_ref().is_empty()) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "replace_many: find patterns must not be empty",
+            ));
+        }
+        let mut counts = vec![0; pairs.len()];
+        let mut edits = Vec::new();
+        {
+            let bytes = self.bytes();
+            let mut next_matches = pairs
+                .iter()
+                .map(|(find, _)| crate::read::find_bytes(bytes, find))
+                .collect::<Vec<Option<usize>>>();
+            loop {
+                let best = next_matches
+                    .iter()
+                    .enumerate()
+                    .filter_map(|(i, offset)| offset.map(|offset| (offset, i)))
+                    .min_by_key(|&(offset, i)| {
+                        (offset, std::cmp::Reverse(pairs[i].0.as_ref().len()), i)
+                    });
+                let Some((offset, i)) = best else {
+                    break;
+                };
+                let (find, replace) = &pairs[i];
+                let end = offset + find.as_ref().len();
+                edits.push((offset..end, replace.as_ref()));
+                counts[i] += 1;
+                let cursor = end;
+                for (j, next_match) in next_matches.iter_mut().enumerate() {
+                    if next_match.is_some_and(|next_offset| next_offset < cursor) {
+                        *next_match = crate::read::find_bytes(&bytes[cursor..], &pairs[j].0)
+                            .map(|found| found + cursor);
+                    }
+                }
+            }
+        }
+        self.splice_ranges(&edits)?;
+        Ok(counts)
+    }
+
+    #[cfg(feature = "search")]
+    /// Calls `replace_with` for each of the provided match offsets of `pattern`, in the order given, and
+    /// applies the replacements it returns. Returns the number of matches that were changed.
+    fn find_replace_with_inner<'r, B, F, I>(
+        &mut self,
+        pattern: B,
+        offsets: I,
+        mut replace_with: F,
+    ) -> io::Result<usize>
+    where
+        B: AsRef<[u8]>,
+        F: FnMut(&[u8], usize) -> Option<Cow<'r, [u8]>>,
+        I: IntoIterator<Item = usize>,
+    {
+        let pattern_len = pattern.as_ref().len();
+        let mut edits = offsets
+            .into_iter()
+            .filter_map(|offset| {
+                let range = offset..offset + pattern_len;
+                replace_with(&self.mmap[range.clone()], offset).map(|bytes| (range, bytes))
+            })
+            .collect::<Vec<(Range<usize>, Cow<'r, [u8]>)>>();
+        edits.sort_by_key(|(range, _)| range.start);
+        self.splice_ranges(&edits)?;
+        Ok(edits.len())
+    }
+
+    #[cfg(feature = "search")]
+    /// Visits every occurrence of `pattern` in the file, in order, calling `replace_with` with the matched bytes and their offset.
+    /// The closure decides what happens to each match: `None` keeps it, `Some` replaces it with the returned bytes,
+    /// and `Some` of an empty slice drops it. Replacements may differ in length from the match; the file is resized at most once.
+    /// Offsets passed to the closure refer to the file as it was before any replacement.
+    /// Returns the number of matches that were replaced or dropped.
+    pub fn find_replace_with<'r, B, F>(&mut self, pattern: B, replace_with: F) -> io::Result<usize>
+    where
+        B: AsRef<[u8]>,
+        F: FnMut(&[u8], usize) -> Option<Cow<'r, [u8]>>,
+    {
+        let offsets = crate::read::find_bytes_all(self.bytes(), &pattern);
+        self.find_replace_with_inner(pattern, offsets, replace_with)
+    }
+
+    #[cfg(feature = "search")]
+    /// Visits every occurrence of `pattern` in the file, in reverse order, calling `replace_with` with the matched bytes and their offset.
+    /// Behaves as `find_replace_with` otherwise.
+    pub fn rfind_replace_with<'r, B, F>(&mut self, pattern: B, replace_with: F) -> io::Result<usize>
+    where
+        B: AsRef<[u8]>,
+        F: FnMut(&[u8], usize) -> Option<Cow<'r, [u8]>>,
+    {
+        let offsets = crate::read::rfind_bytes_all(self.bytes(), &pattern);
+        self.find_replace_with_inner(pattern, offsets, replace_with)
+    }
+
+    #[cfg(feature = "search")]
+    /// Calls `replace_with` for the nth occurrence of `pattern` in the file only.
+    /// If the nth occurrence is not found, the closure is not called.
+    /// Behaves as `find_replace_with` otherwise.
+    pub fn find_replace_nth_with<'r, B, F>(
+        &mut self,
+        pattern: B,
+        n: usize,
+        replace_with: F,
+    ) -> io::Result<usize>
+    where
+        B: AsRef<[u8]>,
+        F: FnMut(&[u8], usize) -> Option<Cow<'r, [u8]>>,
+    {
+        let offset = crate::read::find_bytes_nth(self.bytes(), &pattern, n);
+        self.find_replace_with_inner(pattern, offset, replace_with)
+    }
+
+    #[cfg(feature = "search")]
+    /// Calls `replace_with` for the nth occurrence of `pattern` in the file, in reverse order, only.
+    /// If the nth occurrence is not found, the closure is not called.
+    /// Behaves as `find_replace_with` otherwise.
+    pub fn rfind_replace_nth_with<'r, B, F>(
+        &mut self,
+        pattern: B,
+        n: usize,
+        replace_with: F,
+    ) -> io::Result<usize>
+    where
+        B: AsRef<[u8]>,
+        F: FnMut(&[u8], usize) -> Option<Cow<'r, [u8]>>,
+    {
+        let offset = crate::read::rfind_bytes_nth(self.bytes(), &pattern, n);
+        self.find_replace_with_inner(pattern, offset, replace_with)
+    }
+
     /// Returns a newly-opened `File` object that represents the file being written to.
     pub fn open_new_file(&mut self) -> io::Result<File> {
         open_as_write(self.path.as_ref())
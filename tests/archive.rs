@@ -0,0 +1,51 @@
+#![cfg(feature = "rkyv")]
+use file_rw::{FileReader, FileWriter};
+use std::collections::HashMap;
+use tempfile::tempdir;
+
+#[derive(Debug, PartialEq, rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)]
+struct LookupTable {
+    version: u32,
+    entries: HashMap<String, u64>,
+}
+
+#[test]
+fn test_write_archived() {
+    let tempdir = tempdir().unwrap();
+    let test_file_path = tempdir.path().join("test_write_archived.rkyv");
+    let mut file_writer = FileWriter::open(&test_file_path).unwrap();
+    let table = LookupTable {
+        version: 3,
+        entries: HashMap::from([("alpha".to_string(), 1), ("beta".to_string(), 2)]),
+    };
+    file_writer.write_archived(&table).unwrap();
+
+    let file_reader = FileReader::open(&test_file_path).unwrap();
+    let archived = file_reader.archived::<LookupTable>().unwrap();
+    assert_eq!(archived.version, 3);
+    assert_eq!(
+        archived.entries.get("beta").map(|value| value.to_native()),
+        Some(2)
+    );
+    let bytes = file_reader.as_slice();
+    assert!(bytes
+        .as_ptr_range()
+        .contains(&(archived as *const _ as *const u8)));
+    let deserialized = rkyv::deserialize::<LookupTable, rkyv::rancor::Error>(archived).unwrap();
+    assert_eq!(deserialized, table);
+    assert_eq!(file_writer.archived::<LookupTable>().unwrap().version, 3);
+}
+
+#[test]
+fn test_archived_invalid() {
+    let tempdir = tempdir().unwrap();
+    let test_file_path = tempdir.path().join("test_archived_invalid.rkyv");
+    let mut file_writer = FileWriter::open(&test_file_path).unwrap();
+    file_writer.overwrite([0xffu8; 3]).unwrap();
+    let file_reader = FileReader::open(&test_file_path).unwrap();
+    let err = file_reader
+        .archived::<LookupTable>()
+        .map(|_| ())
+        .unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+}
@@ -0,0 +1,118 @@
+#[macro_use]
+mod common;
+
+use std::io;
+
+#[test]
+fn test_read_at() {
+    let mut bytes = vec![0x12, 0x34];
+    bytes.extend_from_slice(&(-2i64).to_be_bytes());
+    bytes.extend_from_slice(&1.5f32.to_le_bytes());
+    fixture_test!(
+        "test_read_at.bin",
+        &bytes,
+        |_, _, file_writer, file_reader| {
+            assert_eq!(file_reader.read_u16_le_at(0).unwrap(), 0x3412);
+            assert_eq!(file_reader.read_u16_be_at(0).unwrap(), 0x1234);
+            assert_eq!(file_reader.read_i64_be_at(2).unwrap(), -2);
+            assert_eq!(file_reader.read_f32_le_at(10).unwrap(), 1.5);
+            assert_eq!(file_writer.read_f32_le_at(10).unwrap(), 1.5);
+            assert_eq!(
+                file_reader.read_u32_le_at(12).unwrap_err().kind(),
+                io::ErrorKind::UnexpectedEof
+            );
+            assert_eq!(
+                file_reader.read_u16_le_at(usize::MAX).unwrap_err().kind(),
+                io::ErrorKind::UnexpectedEof
+            );
+        }
+    );
+}
+
+#[test]
+fn test_write_at() {
+    fixture_test!(
+        "test_write_at.bin",
+        [0u8; 8],
+        |_, _, mut file_writer, file_reader| {
+            file_writer.write_u32_be_at(0, 0xdeadbeef).unwrap();
+            file_writer.write_i16_le_at(4, -3).unwrap();
+            assert_eq!(
+                file_reader.as_slice(),
+                &[0xde, 0xad, 0xbe, 0xef, 0xfd, 0xff, 0, 0]
+            );
+            assert_eq!(
+                file_writer.write_f64_le_at(4, 1.0).unwrap_err().kind(),
+                io::ErrorKind::UnexpectedEof
+            );
+            assert_eq!(file_writer.len(), 8);
+        }
+    );
+}
+
+#[test]
+fn test_varint() {
+    fixture_test!(
+        "test_varint.bin",
+        [0u8; 16],
+        |_, _, mut file_writer, file_reader| {
+            assert_eq!(file_writer.write_uvarint_at(0, 300).unwrap(), 2);
+            assert_eq!(file_writer.write_varint_at(2, -65).unwrap(), 2);
+            assert_eq!(file_writer.write_uvarint_at(4, u64::MAX).unwrap(), 10);
+            assert_eq!(&file_reader.as_slice()[..4], &[0xac, 0x02, 0x81, 0x01]);
+            assert_eq!(file_reader.read_uvarint_at(0).unwrap(), (300, 2));
+            assert_eq!(file_reader.read_varint_at(2).unwrap(), (-65, 2));
+            assert_eq!(file_reader.read_uvarint_at(4).unwrap(), (u64::MAX, 10));
+            assert_eq!(
+                file_writer
+                    .write_uvarint_at(14, u64::MAX)
+                    .unwrap_err()
+                    .kind(),
+                io::ErrorKind::UnexpectedEof
+            );
+        }
+    );
+    fixture_test!(
+        "test_varint_invalid.bin",
+        [0xffu8; 11],
+        |_, _, _, file_reader| {
+            assert_eq!(
+                file_reader.read_uvarint_at(0).unwrap_err().kind(),
+                io::ErrorKind::InvalidData
+            );
+            assert_eq!(
+                file_reader.read_uvarint_at(5).unwrap_err().kind(),
+                io::ErrorKind::UnexpectedEof
+            );
+        }
+    );
+}
+
+#[test]
+fn test_byte_cursor() {
+    let mut bytes = b"RIFF".to_vec();
+    bytes.extend_from_slice(&36u32.to_le_bytes());
+    bytes.extend_from_slice(&[0x96, 0x01, 0x7f]);
+    fixture_test!(
+        "test_byte_cursor.bin",
+        &bytes,
+        |_, _, file_writer, file_reader| {
+            let mut cursor = file_reader.cursor();
+            assert_eq!(cursor.read_bytes(4).unwrap(), b"RIFF");
+            assert_eq!(cursor.read_u32_le().unwrap(), 36);
+            assert_eq!(cursor.read_uvarint().unwrap(), 150);
+            assert_eq!(cursor.position(), 10);
+            assert_eq!(
+                cursor.read_u16_be().unwrap_err().kind(),
+                io::ErrorKind::UnexpectedEof
+            );
+            assert_eq!(cursor.position(), 10);
+            assert_eq!(cursor.read_i8().unwrap(), 127);
+            assert!(cursor.is_empty());
+            cursor.set_position(0);
+            cursor.skip(4).unwrap();
+            assert_eq!(cursor.remaining(), 7);
+            assert_eq!(file_writer.cursor().read_u32_be().unwrap(), 0x52494646);
+        }
+    );
+}
@@ -0,0 +1,106 @@
+#[macro_use]
+mod common;
+
+use file_rw::{bits::BitOrder, FileReader};
+use std::io;
+
+/// Returns a value with the low `n` bits set.
+fn ones(n: u32) -> u64 {
+    u64::MAX.checked_shr(64 - n).unwrap_or(0)
+}
+
+#[test]
+fn test_bit_reader_msb_first() {
+    fixture_test!(
+        "test_bit_reader_msb_first.bin",
+        [0b1011_0011, 0b0101_1111],
+        |_, _, _, file_reader| {
+            let mut reader = file_reader.bit_reader(BitOrder::MsbFirst);
+            assert!(reader.read_bit().unwrap());
+            assert_eq!(reader.peek_bits(3).unwrap(), 0b011);
+            assert_eq!(reader.read_bits(3).unwrap(), 0b011);
+            assert_eq!(reader.read_bits(6).unwrap(), 0b00_1101);
+            assert_eq!(reader.bit_position(), 10);
+            assert_eq!(
+                reader.read_bits(7).unwrap_err().kind(),
+                io::ErrorKind::UnexpectedEof
+            );
+            assert_eq!(reader.bit_position(), 10);
+            reader.align_to_byte();
+            assert_eq!(reader.bit_position(), 16);
+            assert_eq!(
+                reader.read_bits(65).unwrap_err().kind(),
+                io::ErrorKind::InvalidInput
+            );
+        }
+    );
+}
+
+#[test]
+fn test_bit_reader_lsb_first() {
+    fixture_test!(
+        "test_bit_reader_lsb_first.bin",
+        [0b1011_0011, 0b0101_1111],
+        |_, _, _, file_reader| {
+            let mut reader = file_reader.bit_reader(BitOrder::LsbFirst);
+            assert_eq!(reader.read_bits(3).unwrap(), 0b011);
+            assert_eq!(reader.read_bits(7).unwrap(), 0b111_0110);
+            assert_eq!(reader.remaining_bits(), 6);
+            reader.set_bit_position(0);
+            assert_eq!(reader.read_bits(16).unwrap(), 0b0101_1111_1011_0011);
+        }
+    );
+}
+
+#[test]
+fn test_bit_writer_round_trip() {
+    for order in [BitOrder::MsbFirst, BitOrder::LsbFirst] {
+        fixture_test!(
+            "test_bit_writer_round_trip.bin",
+            "",
+            |_, test_file_path, mut file_writer, _| {
+                file_writer.overwrite("hdr").unwrap();
+                let mut writer = file_writer.bit_writer(order);
+                for n in 0..=64u32 {
+                    writer.write_bits(ones(n), n).unwrap();
+                    writer.write_bit(n % 2 == 0).unwrap();
+                }
+                writer.align_to_byte().unwrap();
+                writer.write_bits(0x5a, 8).unwrap();
+                let bits = writer.bit_position();
+                writer.finish().unwrap();
+                assert_eq!(file_writer.len(), 3 + bits / 8);
+
+                let file_reader = FileReader::open(&test_file_path).unwrap();
+                assert_eq!(&file_reader.as_slice()[..3], b"hdr");
+                let mut reader = file_reader.bit_reader(order);
+                reader.set_bit_position(24);
+                for n in 0..=64u32 {
+                    assert_eq!(
+                        reader.read_bits(n).unwrap(),
+                        u64::MAX >> (64 - n.max(1)) & if n == 0 { 0 } else { u64::MAX }
+                    );
+                    assert_eq!(reader.read_bit().unwrap(), n % 2 == 0);
+                }
+                reader.align_to_byte();
+                assert_eq!(reader.read_bits(8).unwrap(), 0x5a);
+                assert_eq!(reader.remaining_bits(), 0);
+            }
+        );
+    }
+}
+
+#[test]
+fn test_bit_writer_drop_pads() {
+    fixture_test!(
+        "test_bit_writer_drop_pads.bin",
+        "",
+        |_, _, mut file_writer, _| {
+            {
+                let mut writer = file_writer.bit_writer(BitOrder::MsbFirst);
+                writer.write_bits(0b101, 3).unwrap();
+            }
+            assert_eq!(file_writer.bytes(), &[0b1010_0000]);
+        }
+    );
+}
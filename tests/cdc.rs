@@ -0,0 +1,112 @@
+#![cfg(feature = "cdc")]
+#[macro_use]
+mod common;
+
+use file_rw::cdc::CdcChunks;
+use rand::{rngs::StdRng, RngCore, SeedableRng};
+use std::io;
+
+fn random_bytes(len: usize) -> Vec<u8> {
+    let mut bytes = vec![0; len];
+    StdRng::seed_from_u64(50).fill_bytes(&mut bytes);
+    bytes
+}
+
+#[test]
+fn test_cdc_chunks() {
+    let bytes = random_bytes(256 * 1024);
+    fixture_test!("test_cdc_chunks", &bytes, |_, _, _, file_reader| {
+        let chunks: Vec<_> = file_reader.cdc_chunks(2048, 8192, 32768).unwrap().collect();
+        assert!(chunks.len() > 1);
+        let mut offset = 0;
+        for (i, (range, _)) in chunks.iter().enumerate() {
+            assert_eq!(range.start, offset);
+            assert!(range.len() <= 32768);
+            if i + 1 < chunks.len() {
+                assert!(range.len() >= 2048);
+            }
+            offset = range.end;
+        }
+        assert_eq!(offset, bytes.len());
+
+        let again: Vec<_> = file_reader.cdc_chunks(2048, 8192, 32768).unwrap().collect();
+        assert_eq!(chunks, again);
+        let from_slice: Vec<_> = CdcChunks::new(&bytes, 2048, 8192, 32768).unwrap().collect();
+        assert_eq!(chunks, from_slice);
+    });
+}
+
+#[test]
+fn test_cdc_chunks_shift() {
+    let bytes = random_bytes(256 * 1024);
+    let mut edited = bytes.clone();
+    edited.splice(100..100, *b"inserted");
+    let original: Vec<_> = CdcChunks::new(&bytes, 2048, 8192, 32768)
+        .unwrap()
+        .map(|(range, _)| bytes[range].to_vec())
+        .collect();
+    fixture_test!("test_cdc_chunks_shift", &edited, |_, _, _, file_reader| {
+        let chunks: Vec<_> = file_reader
+            .cdc_chunks(2048, 8192, 32768)
+            .unwrap()
+            .map(|(range, _)| file_reader.as_slice()[range].to_vec())
+            .collect();
+        assert_ne!(chunks[0], original[0]);
+        assert_eq!(chunks[1..], original[1..]);
+    });
+}
+
+#[test]
+fn test_cdc_chunks_empty() {
+    fixture_test!("test_cdc_chunks_empty", "", |_, _, _, file_reader| {
+        assert_eq!(file_reader.cdc_chunks(64, 256, 1024).unwrap().count(), 0);
+    });
+}
+
+#[test]
+fn test_cdc_chunks_invalid_sizes() {
+    fixture_test!(
+        "test_cdc_chunks_invalid_sizes",
+        "data",
+        |_, _, _, file_reader| {
+            for (min, avg, max) in [
+                (32, 256, 1024),
+                (64, 128, 1024),
+                (64, 256, 512),
+                (64, 256, 32 * 1024 * 1024),
+                (8192, 4096, 16384),
+                (64, 8192, 4096),
+            ] {
+                let err = file_reader
+                    .cdc_chunks(min, avg, max)
+                    .map(|_| ())
+                    .unwrap_err();
+                assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+            }
+        }
+    );
+}
+
+#[cfg(feature = "sha3_256")]
+#[test]
+fn test_cdc_chunks_with() {
+    use sha3::{Digest, Sha3_256};
+
+    let bytes = random_bytes(64 * 1024);
+    fixture_test!("test_cdc_chunks_with", &bytes, |_, _, _, file_reader| {
+        let ranges: Vec<_> = file_reader
+            .cdc_chunks(1024, 4096, 16384)
+            .unwrap()
+            .map(|(range, _)| range)
+            .collect();
+        let hashed: Vec<_> = file_reader
+            .cdc_chunks_with::<Sha3_256>(1024, 4096, 16384)
+            .unwrap()
+            .collect();
+        assert_eq!(hashed.len(), ranges.len());
+        for ((range, hash), expected) in hashed.into_iter().zip(ranges) {
+            assert_eq!(hash, Sha3_256::digest(&bytes[expected.clone()]));
+            assert_eq!(range, expected);
+        }
+    });
+}
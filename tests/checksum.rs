@@ -0,0 +1,79 @@
+#![cfg(feature = "checksum")]
+#[macro_use]
+mod common;
+
+use file_rw::{
+    checksum::{compare_files_checksum, Adler32, Checksum, Crc32, Crc32c, XxHash64, Xxh3},
+    FileWriter,
+};
+use std::io;
+
+#[test]
+fn test_checksum() {
+    fixture_test!("test_checksum", "123456789", |_, _, _, file_reader| {
+        assert_eq!(file_reader.checksum::<Crc32>(), 0xcbf43926);
+        assert_eq!(file_reader.checksum::<Crc32c>(), 0xe3069283);
+        assert_eq!(file_reader.checksum::<Adler32>(), 0x091e01de);
+        assert_eq!(XxHash64::checksum(b""), 0xef46db3751d8e999);
+        assert_eq!(Xxh3::checksum(b""), 0x2d06800538d394c2);
+        assert_eq!(
+            file_reader.checksum::<XxHash64>(),
+            XxHash64::checksum(b"123456789")
+        );
+        assert_eq!(file_reader.checksum::<Xxh3>(), Xxh3::checksum(b"123456789"));
+        assert_eq!(file_reader.checksum_to_string::<Crc32>(), "cbf43926");
+        assert!(file_reader.compare_checksum::<Crc32c>(0xe3069283));
+        assert!(!file_reader.compare_checksum::<Crc32c>(0));
+    });
+}
+
+#[test]
+fn test_checksum_range() {
+    fixture_test!(
+        "test_checksum_range",
+        "xx123456789xx",
+        |_, _, _, file_reader| {
+            assert_eq!(
+                file_reader.checksum_range::<Crc32>(2..11).unwrap(),
+                0xcbf43926
+            );
+            assert_eq!(
+                file_reader
+                    .checksum_range::<Crc32>(2..14)
+                    .unwrap_err()
+                    .kind(),
+                io::ErrorKind::UnexpectedEof
+            );
+            let mut checksum = Adler32::default();
+            file_reader.update_checksum(&mut checksum);
+            file_reader.update_checksum(&mut checksum);
+            assert_eq!(
+                checksum.finalize(),
+                Adler32::checksum(b"xx123456789xxxx123456789xx")
+            );
+        }
+    );
+}
+
+#[test]
+fn test_compare_files_checksum() {
+    fixture_test!("test_compare_1", "same", |tempdir, _, _, file_reader| {
+        let same_path = tempdir.path().join("test_compare_2");
+        let diff_path = tempdir.path().join("test_compare_3");
+        FileWriter::open(&same_path)
+            .unwrap()
+            .overwrite("same")
+            .unwrap();
+        FileWriter::open(&diff_path)
+            .unwrap()
+            .overwrite("diff")
+            .unwrap();
+        assert!(file_reader.compare_to_file_at_path_checksum::<Xxh3>(&same_path));
+        assert!(!file_reader.compare_to_file_at_path_checksum::<Xxh3>(&diff_path));
+        assert!(compare_files_checksum::<Crc32c>(
+            &same_path,
+            tempdir.path().join("test_compare_1")
+        ));
+        assert!(!compare_files_checksum::<Crc32c>(&same_path, &diff_path));
+    });
+}
@@ -0,0 +1,93 @@
+#[macro_use]
+mod common;
+
+use file_rw::{
+    chunk::{ChunkLayout, Endian},
+    FileReader,
+};
+use std::io;
+
+#[test]
+fn test_riff_chunks() {
+    fixture_test!(
+        "test_riff_chunks.wav",
+        b"RIFF\0\0\0\0WAVE",
+        |_, test_file_path, mut file_writer, _| {
+            let mut chunk_writer = file_writer.chunk_writer(ChunkLayout::riff());
+            let fmt = chunk_writer.write_chunk("fmt ", [1u8; 16]).unwrap();
+            let data = chunk_writer.write_chunk(b"data", [2u8; 3]).unwrap();
+            assert_eq!(fmt, 20..36);
+            assert_eq!(data, 44..47);
+            assert_eq!(
+                chunk_writer.write_chunk("toolong", []).unwrap_err().kind(),
+                io::ErrorKind::InvalidInput
+            );
+            assert_eq!(file_writer.len(), 48);
+            assert_eq!(&file_writer.bytes()[4..8], &40u32.to_le_bytes());
+
+            let file_reader = FileReader::open(&test_file_path).unwrap();
+            let chunks = file_reader
+                .chunks(ChunkLayout::riff())
+                .collect::<io::Result<Vec<_>>>()
+                .unwrap();
+            assert_eq!(chunks, vec![(&b"fmt "[..], 20..36), (&b"data"[..], 44..47)]);
+            assert_eq!(&file_reader.as_slice()[chunks[1].1.clone()], &[2, 2, 2]);
+        }
+    );
+}
+
+#[test]
+fn test_png_chunks() {
+    fixture_test!(
+        "test_png_chunks.png",
+        b"\x89PNG\r\n\x1a\n",
+        |_, _, mut file_writer, _| {
+            let mut chunk_writer = file_writer.chunk_writer(ChunkLayout::png());
+            chunk_writer.write_chunk("IEND", []).unwrap();
+            assert_eq!(&file_writer.bytes()[8..], b"\0\0\0\0IEND\xae\x42\x60\x82");
+            let tags = file_writer
+                .chunks(ChunkLayout::png())
+                .map(|chunk| chunk.unwrap().0)
+                .collect::<Vec<_>>();
+            assert_eq!(tags, vec![b"IEND"]);
+        }
+    );
+}
+
+#[test]
+fn test_truncated_chunk() {
+    let mut bytes = b"FORM\0\0\0\x20AIFF".to_vec();
+    bytes.extend_from_slice(b"COMM\0\0\0\x02ab");
+    bytes.extend_from_slice(b"SSND\0\0\0\x10abc");
+    fixture_test!(
+        "test_truncated_chunk.aiff",
+        &bytes,
+        |_, _, mut file_writer, file_reader| {
+            let mut chunks = file_reader.chunks(ChunkLayout::iff());
+            assert_eq!(chunks.next().unwrap().unwrap(), (&b"COMM"[..], 20..22));
+            assert_eq!(
+                chunks.next().unwrap().unwrap_err().kind(),
+                io::ErrorKind::UnexpectedEof
+            );
+            assert!(chunks.next().is_none());
+            let layout = ChunkLayout {
+                tag_len: 2,
+                length_len: 1,
+                length_endian: Endian::Little,
+                length_first: false,
+                padding: 1,
+                crc_len: 0,
+                start: 0,
+                container_length_at: None,
+            };
+            let mut chunk_writer = file_writer.chunk_writer(layout);
+            assert_eq!(
+                chunk_writer
+                    .write_chunk("ab", [0u8; 256])
+                    .unwrap_err()
+                    .kind(),
+                io::ErrorKind::InvalidInput
+            );
+        }
+    );
+}
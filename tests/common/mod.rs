@@ -0,0 +1,17 @@
+/// Creates `$file_name` in a fresh temporary directory with `$init_bytes` as its contents, then runs
+/// `$block` with the directory, the file's path, and a `FileWriter` and `FileReader` opened on it.
+/// Each binding is a pattern, so tests can ignore what they don't use with `_` or take `mut file_writer`.
+macro_rules! fixture_test {
+    ($file_name:expr, $init_bytes:expr, |$tempdir:pat_param, $test_file_path:pat_param, $file_writer:pat_param, $file_reader:pat_param| $block:block) => {{
+        let tempdir = tempfile::tempdir().unwrap();
+        let test_file_path = tempdir.path().join($file_name);
+        let mut file_writer = file_rw::FileWriter::open(&test_file_path).unwrap();
+        file_writer.overwrite($init_bytes).unwrap();
+        let file_reader = file_rw::FileReader::open(&test_file_path).unwrap();
+        let $tempdir = &tempdir;
+        let $test_file_path = &test_file_path;
+        let $file_writer = file_writer;
+        let $file_reader = file_reader;
+        $block
+    }};
+}
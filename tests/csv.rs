@@ -0,0 +1,93 @@
+#![cfg(feature = "csv")]
+#[macro_use]
+mod common;
+
+use file_rw::csv::CsvOptions;
+
+const PEOPLE_CSV: &str =
+    "name,age,note\r\nalice,30,\"likes \"\"quotes\"\"\"\r\nbob,25,\"multi\nline\"\r\ncarol,41\r\n";
+
+#[test]
+fn test_csv_records() {
+    fixture_test!(
+        "test_csv_records.csv",
+        PEOPLE_CSV,
+        |_, _, _, file_reader| {
+            let csv = file_reader.csv();
+            let header = csv.header().unwrap();
+            assert_eq!(
+                header.fields().collect::<Vec<_>>(),
+                vec![&b"name"[..], b"age", b"note"]
+            );
+            let records = csv.records().collect::<Vec<_>>();
+            assert_eq!(records.len(), 3);
+            let note = records[0].field(2).unwrap();
+            assert_eq!(note, br#"likes ""quotes"""#);
+            assert_eq!(&records[0].unescape(note)[..], br#"likes "quotes""#);
+            assert_eq!(records[1].field(2).unwrap(), b"multi\nline");
+            assert_eq!(records[2].len(), 2);
+            assert_eq!(records[2].as_bytes(), b"carol,41");
+            let bytes = file_reader.as_slice();
+            assert!(bytes.as_ptr_range().contains(&note.as_ptr()));
+        }
+    );
+}
+
+#[test]
+fn test_csv_column() {
+    fixture_test!("test_csv_column.csv", PEOPLE_CSV, |_, _, _, file_reader| {
+        let csv = file_reader.csv();
+        assert_eq!(csv.column_index("age"), Some(1));
+        assert_eq!(
+            csv.column("age").unwrap().collect::<Vec<_>>(),
+            vec![&b"30"[..], b"25", b"41"]
+        );
+        assert_eq!(csv.column("note").unwrap().last().unwrap(), b"");
+        assert_eq!(
+            csv.column("missing").err().unwrap().kind(),
+            std::io::ErrorKind::NotFound
+        );
+    });
+}
+
+#[test]
+fn test_csv_index() {
+    fixture_test!(
+        "test_csv_index.tsv",
+        "a\tb\n1\t2\n3\t4\n5\t6",
+        |_, _, _, file_reader| {
+            let mut tsv = file_reader.csv_with(CsvOptions {
+                has_header: false,
+                ..CsvOptions::tsv()
+            });
+            assert!(tsv.header().is_none());
+            assert!(tsv.index().is_none());
+            assert_eq!(tsv.build_index(), &[0, 4, 8, 12]);
+            assert_eq!(tsv.record_count(), 4);
+            let record = tsv.record(3).unwrap();
+            assert_eq!(record.offset(), 12);
+            assert_eq!(record.field(1).unwrap(), b"6");
+            assert_eq!(tsv.record_range(1), Some(4..7));
+            assert!(tsv.record(4).is_none());
+        }
+    );
+}
+
+#[test]
+fn test_csv_blank_lines() {
+    fixture_test!(
+        "test_csv_blank_lines.csv",
+        "\r\nh1,h2\r\na,b\r\nc,d\r\n\r\n\ne,f\r\n\r\n",
+        |_, _, _, file_reader| {
+            let mut csv = file_reader.csv();
+            assert_eq!(csv.header().unwrap().as_bytes(), b"h1,h2");
+            assert_eq!(
+                csv.column("h2").unwrap().collect::<Vec<_>>(),
+                vec![&b"b"[..], b"d", b"f"]
+            );
+            assert_eq!(csv.record_count(), 3);
+            assert_eq!(csv.build_index(), &[9, 14, 22]);
+            assert_eq!(csv.record(2).unwrap().as_bytes(), b"e,f");
+        }
+    );
+}
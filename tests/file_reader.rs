@@ -19,7 +19,6 @@ macro_rules! file_reader_test {
 }
 
 #[cfg(test)]
-
 fn create_test_files(dir: &Path) {
     let mut file = File::create(dir.join("test_file")).unwrap();
     file.write_all(b"test file\n").unwrap();
@@ -174,6 +173,118 @@ fn test_hash_to_string() {
     );
 }
 
+#[cfg(feature = "sha3_256")]
+#[test]
+fn test_hash_range_with() {
+    file_reader_test!(
+        "test_file",
+        |tempdir, tempdir_path, test_file_path, _file_writer, file_reader| {
+            let expected_hash = sha3::Sha3_256::digest(b"file");
+            assert_eq!(
+                file_reader.hash_range_with::<sha3::Sha3_256>(5..9).unwrap(),
+                expected_hash
+            );
+            assert_eq!(
+                file_reader
+                    .hash_range_with::<sha3::Sha3_256>(5..11)
+                    .unwrap_err()
+                    .kind(),
+                std::io::ErrorKind::UnexpectedEof
+            );
+        }
+    );
+}
+
+#[cfg(feature = "sha3_256")]
+#[test]
+fn test_update_hasher() {
+    file_reader_test!(
+        "test_file",
+        |tempdir, tempdir_path, test_file_path, _file_writer, file_reader| {
+            let diff_reader = FileReader::open(tempdir_path.join("test_file_diff")).unwrap();
+            let mut hasher = sha3::Sha3_256::new();
+            file_reader.update_hasher(&mut hasher);
+            diff_reader.update_hasher(&mut hasher);
+            let expected_hash = sha3::Sha3_256::digest(b"test file\ntest file diff\n");
+            assert_eq!(hasher.finalize(), expected_hash);
+        }
+    );
+}
+
+#[cfg(feature = "sha3_256")]
+#[test]
+fn test_hash_multi() {
+    file_reader_test!(
+        "test_file",
+        |tempdir, tempdir_path, test_file_path, _file_writer, file_reader| {
+            let (sha3_256, sha3_512, keccak) =
+                file_reader.hash_multi::<(sha3::Sha3_256, sha3::Sha3_512, sha3::Keccak256)>();
+            assert_eq!(sha3_256, file_reader.hash_with::<sha3::Sha3_256>());
+            assert_eq!(sha3_512, file_reader.hash_with::<sha3::Sha3_512>());
+            assert_eq!(keccak, file_reader.hash_with::<sha3::Keccak256>());
+        }
+    );
+}
+
+#[cfg(all(feature = "sha3_256", feature = "hash_encoding"))]
+#[test]
+fn test_compare_hash_str() {
+    file_reader_test!(
+        "test_file",
+        |tempdir, tempdir_path, test_file_path, _file_writer, file_reader| {
+            let hex = file_reader.hash_to_string();
+            assert!(file_reader.compare_hash_str(&hex));
+            assert!(file_reader.compare_hash_str(&hex.to_uppercase()));
+            let base64 = file_reader
+                .hash_to_encoded_string_with::<sha3::Sha3_256>(file_rw::hash::HashEncoding::Base64)
+                .unwrap();
+            assert!(file_reader.compare_hash_str(&base64));
+            assert!(!file_reader.compare_hash_str("not a hash"));
+            assert!(!file_reader.compare_hash_str(&hex[2..]));
+        }
+    );
+}
+
+#[cfg(feature = "blake3")]
+#[test]
+fn test_blake3() {
+    file_reader_test!(
+        "test_file",
+        |tempdir, tempdir_path, test_file_path, file_writer, file_reader| {
+            let expected_hash = blake3::hash(b"test file\n");
+            assert_eq!(file_reader.blake3(), expected_hash);
+            assert_eq!(
+                file_reader.blake3_to_string(),
+                expected_hash.to_hex().as_str()
+            );
+            assert!(file_reader.compare_hash_blake3(&expected_hash));
+
+            let large = vec![7u8; 1024 * 1024];
+            file_writer.overwrite(&large).unwrap();
+            let file_reader = FileReader::open(&test_file_path).unwrap();
+            assert_eq!(file_reader.blake3(), blake3::hash(&large));
+        }
+    );
+}
+
+#[cfg(feature = "blake3")]
+#[test]
+fn test_compare_files_blake3() {
+    file_reader_test!(
+        "test_file",
+        |tempdir, tempdir_path, test_file_path, _file_writer, _file_reader| {
+            assert!(file_rw::read::compare_files_blake3(
+                &test_file_path,
+                tempdir_path.join("test_file2")
+            ));
+            assert!(!file_rw::read::compare_files_blake3(
+                &test_file_path,
+                tempdir_path.join("test_file_diff")
+            ));
+        }
+    );
+}
+
 #[cfg(feature = "search")]
 #[test]
 fn test_find_as_slice() {
@@ -266,3 +266,90 @@ fn test_mmap() {
         }
     )
 }
+
+#[cfg(feature = "search")]
+#[test]
+fn test_replace_many() {
+    file_writer_test!(
+        "test_replace_many",
+        "Hello, world! Hello!",
+        |tempdir, tempdir_path, test_file_path, file_writer, _file_reader| {
+            let counts = file_writer
+                .replace_many(&[("Hello", "world"), ("world", "Hello"), ("!", "?!")])
+                .unwrap();
+            assert_eq!(counts, vec![2, 1, 2]);
+            assert_eq!(file_writer.bytes(), b"world, Hello?! world?!");
+        }
+    );
+}
+
+#[cfg(feature = "search")]
+#[test]
+fn test_replace_many_prefers_longest() {
+    file_writer_test!(
+        "test_replace_many_prefers_longest",
+        "aaab",
+        |tempdir, tempdir_path, test_file_path, file_writer, _file_reader| {
+            let counts = file_writer
+                .replace_many(&[("a", "x"), ("aab", "")])
+                .unwrap();
+            assert_eq!(counts, vec![1, 1]);
+            assert_eq!(file_writer.bytes(), b"x");
+            assert!(file_writer.replace_many(&[("", "x")]).is_err());
+        }
+    );
+}
+
+#[cfg(feature = "search")]
+#[test]
+fn test_find_replace_with() {
+    file_writer_test!(
+        "test_find_replace_with",
+        "v1 v2 v3 v4",
+        |tempdir, tempdir_path, test_file_path, file_writer, _file_reader| {
+            let mut seen = Vec::new();
+            let changed = file_writer
+                .find_replace_with("v", |matched, offset| {
+                    seen.push(offset);
+                    match offset {
+                        0 => None,
+                        3 => Some(std::borrow::Cow::Borrowed(&b""[..])),
+                        _ => Some(std::borrow::Cow::Owned([matched, b"ersion"].concat())),
+                    }
+                })
+                .unwrap();
+            assert_eq!(seen, vec![0, 3, 6, 9]);
+            assert_eq!(changed, 3);
+            assert_eq!(file_writer.bytes(), b"v1 2 version3 version4");
+        }
+    );
+}
+
+#[cfg(feature = "search")]
+#[test]
+fn test_rfind_replace_with() {
+    file_writer_test!(
+        "test_rfind_replace_with",
+        "a-a-a",
+        |tempdir, tempdir_path, test_file_path, file_writer, _file_reader| {
+            let mut counter = 0;
+            file_writer
+                .rfind_replace_with("a", |_matched, _offset| {
+                    counter += 1;
+                    Some(counter.to_string().into_bytes().into())
+                })
+                .unwrap();
+            assert_eq!(file_writer.bytes(), b"3-2-1");
+
+            file_writer
+                .find_replace_nth_with("-", 1, |_matched, _offset| Some(b"+++"[..].into()))
+                .unwrap();
+            assert_eq!(file_writer.bytes(), b"3-2+++1");
+
+            file_writer
+                .rfind_replace_nth_with("-", 0, |_matched, _offset| Some(b""[..].into()))
+                .unwrap();
+            assert_eq!(file_writer.bytes(), b"32+++1");
+        }
+    );
+}
@@ -0,0 +1,81 @@
+#![cfg(all(feature = "sha2", feature = "hash_encoding"))]
+use file_rw::hash::{parse_hash_str, HashEncoding};
+use sha2::{Digest, Sha256, Sha384};
+use std::io;
+
+const EMPTY_SHA256_HEX: &str = "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855";
+const EMPTY_SHA256_BASE64: &str = "47DEQpj8HBSa+/TImW+5JCeuQeRkm5NMpJWZG3hSuFU=";
+
+#[test]
+fn test_hash_encoding_encode() {
+    let digest = Sha256::digest(b"");
+    let encode = |encoding: HashEncoding| encoding.encode::<Sha256>(&digest).unwrap();
+    assert_eq!(encode(HashEncoding::HexLower), EMPTY_SHA256_HEX);
+    assert_eq!(
+        encode(HashEncoding::HexUpper),
+        EMPTY_SHA256_HEX.to_uppercase()
+    );
+    assert_eq!(encode(HashEncoding::Base64), EMPTY_SHA256_BASE64);
+    assert_eq!(
+        encode(HashEncoding::Base64Url),
+        "47DEQpj8HBSa-_TImW-5JCeuQeRkm5NMpJWZG3hSuFU"
+    );
+    assert_eq!(
+        encode(HashEncoding::Sri),
+        format!("sha256-{}", EMPTY_SHA256_BASE64)
+    );
+    assert!(encode(HashEncoding::Multihash).starts_with("zQm"));
+    let sha3_digest = sha3::Sha3_256::digest(b"");
+    assert_eq!(
+        HashEncoding::Sri
+            .encode::<sha3::Sha3_256>(&sha3_digest)
+            .unwrap_err()
+            .kind(),
+        io::ErrorKind::InvalidInput
+    );
+}
+
+#[test]
+fn test_hash_encoding_round_trip() {
+    let digest = Sha384::digest(b"file_rw");
+    for encoding in [
+        HashEncoding::HexLower,
+        HashEncoding::HexUpper,
+        HashEncoding::Base64,
+        HashEncoding::Base64Url,
+        HashEncoding::Sri,
+        HashEncoding::Multihash,
+    ] {
+        let encoded = encoding.encode::<Sha384>(&digest).unwrap();
+        assert_eq!(encoding.decode::<Sha384>(&encoded).unwrap(), digest);
+        assert_eq!(parse_hash_str::<Sha384>(&encoded).unwrap(), digest);
+    }
+}
+
+#[test]
+fn test_parse_hash_str() {
+    let digest = Sha256::digest(b"");
+    let multihash = HashEncoding::Multihash.encode::<Sha256>(&digest).unwrap();
+    assert_eq!(parse_hash_str::<Sha256>(&multihash[1..]).unwrap(), digest);
+    assert_eq!(
+        parse_hash_str::<Sha256>(&format!(
+            "sha512-AAAA sha256-{}?opt",
+            EMPTY_SHA256_BASE64.trim_end_matches('=')
+        ))
+        .unwrap(),
+        digest
+    );
+    assert_eq!(
+        parse_hash_str::<Sha384>(&format!("sha256-{}", EMPTY_SHA256_BASE64))
+            .unwrap_err()
+            .kind(),
+        io::ErrorKind::InvalidData
+    );
+    assert_eq!(
+        parse_hash_str::<sha3::Sha3_256>(&multihash)
+            .unwrap_err()
+            .kind(),
+        io::ErrorKind::InvalidData
+    );
+    assert!(parse_hash_str::<Sha256>(&EMPTY_SHA256_HEX[1..]).is_err());
+}
@@ -0,0 +1,143 @@
+#![cfg(feature = "json")]
+#[macro_use]
+mod common;
+
+use std::io;
+
+const PACKAGE_JSON: &str = r#"{
+  "name": "file_rw",
+  "version": "0.6.2",
+  "scripts": {
+    "test": "cargo test"
+  },
+  "keywords": ["mmap", "io"],
+  "a/b": {"~c": true}
+}
+"#;
+
+#[test]
+fn test_json_get() {
+    fixture_test!(
+        "test_json_get.json",
+        PACKAGE_JSON,
+        |_, _, _, file_reader| {
+            let bytes = file_reader.as_slice();
+            let range = file_reader.json_get("/version").unwrap();
+            assert_eq!(&bytes[range], br#""0.6.2""#);
+            let range = file_reader.json_get("/scripts/test").unwrap();
+            assert_eq!(&bytes[range], br#""cargo test""#);
+            let range = file_reader.json_get("/keywords/1").unwrap();
+            assert_eq!(&bytes[range], br#""io""#);
+            let range = file_reader.json_get("/a~1b/~0c").unwrap();
+            assert_eq!(&bytes[range], b"true");
+            let range = file_reader.json_get("").unwrap();
+            assert_eq!(&bytes[range], PACKAGE_JSON.trim_end().as_bytes());
+            let err = file_reader.json_get("/keywords/2").unwrap_err();
+            assert_eq!(err.kind(), std::io::ErrorKind::NotFound);
+            assert!(file_reader.json_get("/keywords/01").is_err());
+        }
+    );
+}
+
+#[test]
+fn test_json_set() {
+    fixture_test!(
+        "test_json_set.json",
+        PACKAGE_JSON,
+        |_, _, mut file_writer, _| {
+            file_writer.json_set("/version", r#""0.7.0""#).unwrap();
+            file_writer
+                .json_set("/scripts/bench", r#""cargo bench""#)
+                .unwrap();
+            file_writer.json_set("/keywords/-", r#""hash""#).unwrap();
+            file_writer.json_set("/private", "true").unwrap();
+            assert_eq!(
+                std::str::from_utf8(file_writer.bytes()).unwrap(),
+                r#"{
+  "name": "file_rw",
+  "version": "0.7.0",
+  "scripts": {
+    "test": "cargo test",
+    "bench": "cargo bench"
+  },
+  "keywords": ["mmap", "io", "hash"],
+  "a/b": {"~c": true},
+  "private": true
+}
+"#
+            );
+            assert!(file_writer.json_set("/version", "not json").is_err());
+            assert!(file_writer.json_set("/missing/key", "1").is_err());
+        }
+    );
+    fixture_test!(
+        "test_json_set_empty.json",
+        "{}",
+        |_, _, mut file_writer, _| {
+            file_writer.json_set("/list", "[]").unwrap();
+            file_writer.json_set("/list/0", "1").unwrap();
+            assert_eq!(file_writer.bytes(), br#"{"list": [1]}"#);
+        }
+    );
+}
+
+#[test]
+fn test_json_remove() {
+    fixture_test!(
+        "test_json_remove.json",
+        PACKAGE_JSON,
+        |_, _, mut file_writer, _| {
+            assert!(file_writer.json_remove("/name").unwrap());
+            assert!(file_writer.json_remove("/a~1b").unwrap());
+            assert!(file_writer.json_remove("/keywords/0").unwrap());
+            assert!(file_writer.json_remove("/scripts/test").unwrap());
+            assert!(!file_writer.json_remove("/scripts/test").unwrap());
+            assert_eq!(
+                std::str::from_utf8(file_writer.bytes()).unwrap(),
+                r#"{
+  "version": "0.6.2",
+  "scripts": {},
+  "keywords": ["io"]
+}
+"#
+            );
+            assert!(file_writer.json_remove("").is_err());
+        }
+    );
+}
+
+#[test]
+fn test_json_set_invalid_value() {
+    fixture_test!(
+        "test_json_set_invalid_value.json",
+        r#"{"a": 1}"#,
+        |_, _, mut file_writer, _| {
+            for value in [
+                "nope", "tru", "nul", "01", "1.", "-", "1e", "+1", "1.5e+", "0x10",
+            ] {
+                let err = file_writer.json_set("/a", value).unwrap_err();
+                assert_eq!(err.kind(), io::ErrorKind::InvalidData, "{}", value);
+            }
+            assert_eq!(file_writer.bytes(), br#"{"a": 1}"#);
+            for value in ["true", "false", "null", "0", "-0.5", "12e3", "1.5E-7"] {
+                file_writer.json_set("/a", value).unwrap();
+                assert_eq!(file_writer.json_get("/a").unwrap(), 6..6 + value.len());
+            }
+        }
+    );
+}
+
+#[test]
+fn test_json_nesting_limit() {
+    fixture_test!(
+        "test_json_nesting_limit.json",
+        "[".repeat(200_000),
+        |_, _, mut file_writer, _| {
+            let err = file_writer.json_get("/0").unwrap_err();
+            assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+            let nested = format!("{}{}", "[".repeat(100), "]".repeat(100));
+            file_writer.overwrite(&nested).unwrap();
+            assert_eq!(file_writer.json_get("/0/0").unwrap(), 2..198);
+        }
+    );
+}
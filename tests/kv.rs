@@ -0,0 +1,102 @@
+#[macro_use]
+mod common;
+
+use file_rw::kv::KvEditor;
+
+#[test]
+fn test_kv_get() {
+    fixture_test!(
+        "test_kv_get.env",
+        "# comment\nexport NAME=\"file rw\"\nPORT = 8080\n",
+        |_, _, file_writer, _| {
+            let editor = KvEditor::open(file_writer);
+            assert_eq!(editor.get(None, "NAME").unwrap(), "file rw");
+            assert_eq!(editor.get(None, "PORT").unwrap(), "8080");
+            assert!(editor.get(None, "comment").is_none());
+            let entries = editor
+                .iter()
+                .map(|(section, key, value)| (section, key, value.into_owned()))
+                .collect::<Vec<_>>();
+            assert_eq!(
+                entries,
+                vec![
+                    (None, "NAME", "file rw".to_string()),
+                    (None, "PORT", "8080".to_string())
+                ]
+            );
+        }
+    );
+}
+
+#[test]
+fn test_kv_set_preserves_formatting() {
+    fixture_test!(
+        "test_kv_set.ini",
+        "; top\r\nglobal = 1\r\n\r\n[server]\r\nhost = 'localhost'  \r\nport = 80\r\n\r\n[client]\r\nretries = 3",
+        |_, _, file_writer, _| {
+            let mut editor = KvEditor::open(file_writer);
+            editor.set(Some("server"), "host", "example.com");
+            editor.set(Some("server"), "tls", "true");
+            editor.set(Some("client"), "timeout", "5");
+            editor.set(None, "debug", "false");
+            editor.set(Some("new"), "key", "value");
+            assert!(editor.remove(Some("server"), "port"));
+            assert!(!editor.remove(Some("server"), "missing"));
+            assert_eq!(editor.get(Some("server"), "host").unwrap(), "example.com");
+            assert!(editor.is_dirty());
+            editor.save().unwrap();
+            assert!(!editor.is_dirty());
+            assert_eq!(
+                editor.writer().bytes(),
+                &b"; top\r\nglobal = 1\r\ndebug = false\r\n\r\n[server]\r\nhost = 'example.com'  \r\ntls = true\r\n\r\n[client]\r\nretries = 3\r\ntimeout = 5\r\n\r\n[new]\r\nkey = value\r\n"[..]
+            );
+            assert_eq!(
+                editor.sections().collect::<Vec<_>>(),
+                vec!["server", "client", "new"]
+            );
+            assert_eq!(editor.get(Some("new"), "key").unwrap(), "value");
+        }
+    );
+}
+
+#[test]
+fn test_kv_sysctl() {
+    fixture_test!(
+        "test_kv_sysctl.conf",
+        "net.ipv4.ip_forward=0\n",
+        |_, _, file_writer, _| {
+            let mut editor = KvEditor::open(file_writer);
+            editor.set(None, "net.ipv4.ip_forward", "1");
+            editor.set(None, "vm.swappiness", "10");
+            let file_writer = editor.into_writer().unwrap();
+            assert_eq!(
+                file_writer.bytes(),
+                b"net.ipv4.ip_forward=1\nvm.swappiness=10\n"
+            );
+        }
+    );
+}
+
+#[test]
+fn test_kv_inline_comments() {
+    fixture_test!(
+        "test_kv_inline_comments.ini",
+        "A=1 # c\nB = \"x # y\" ; quoted\nC= # empty\nURL=http://host/#anchor\n",
+        |_, _, file_writer, _| {
+            let mut editor = KvEditor::open(file_writer);
+            assert_eq!(editor.get(None, "A").unwrap(), "1");
+            assert_eq!(editor.get(None, "B").unwrap(), "x # y");
+            assert_eq!(editor.get(None, "C").unwrap(), "");
+            assert_eq!(editor.get(None, "URL").unwrap(), "http://host/#anchor");
+            editor.set(None, "A", "2");
+            editor.set(None, "B", "z");
+            editor.set(None, "C", "3");
+            assert_eq!(editor.get(None, "A").unwrap(), "2");
+            let file_writer = editor.into_writer().unwrap();
+            assert_eq!(
+                file_writer.bytes(),
+                b"A=2 # c\nB = \"z\" ; quoted\nC= 3 # empty\nURL=http://host/#anchor\n"
+            );
+        }
+    );
+}
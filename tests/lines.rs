@@ -0,0 +1,271 @@
+#[macro_use]
+mod common;
+
+use file_rw::lines::{LineEnding, LineIndex, LineMatch, LinePosition};
+use std::borrow::Cow;
+
+#[test]
+fn test_line_index() {
+    let index = LineIndex::new(b"one\r\ntwo\r\n\r\nfour");
+    assert_eq!(index.len(), 4);
+    assert_eq!(index.ending(), LineEnding::CrLf);
+    assert_eq!(index.line(1), Some(5..8));
+    assert_eq!(index.line_with_ending(1), Some(5..10));
+    assert_eq!(index.line(2), Some(10..10));
+    assert_eq!(index.line(3), Some(12..16));
+    assert_eq!(index.line(4), None);
+    assert!(!index.ends_with_newline());
+    assert_eq!(index.line_at_offset(9), Some(1));
+
+    let index = LineIndex::new(b"one\ntwo\n");
+    assert_eq!(index.len(), 2);
+    assert_eq!(index.ending(), LineEnding::Lf);
+    assert!(index.ends_with_newline());
+    assert!(LineIndex::new(b"").is_empty());
+}
+
+#[test]
+fn test_replace_line() {
+    fixture_test!(
+        "test_replace_line",
+        "a\r\nb\r\nc",
+        |_, _, mut file_writer, _| {
+            file_writer.replace_line(1, "bee").unwrap();
+            assert_eq!(file_writer.bytes(), b"a\r\nbee\r\nc");
+            file_writer.replace_line(2, "").unwrap();
+            assert_eq!(file_writer.bytes(), b"a\r\nbee\r\n");
+            assert!(file_writer.replace_line(3, "d").is_err());
+        }
+    );
+}
+
+#[test]
+fn test_insert_line() {
+    fixture_test!("test_insert_line", "a\r\nc", |_, _, mut file_writer, _| {
+        file_writer.insert_line(1, "b").unwrap();
+        assert_eq!(file_writer.bytes(), b"a\r\nb\r\nc");
+        file_writer.insert_line(3, "d").unwrap();
+        assert_eq!(file_writer.bytes(), b"a\r\nb\r\nc\r\nd");
+        file_writer.insert_line(0, "_").unwrap();
+        assert_eq!(file_writer.bytes(), b"_\r\na\r\nb\r\nc\r\nd");
+        assert!(file_writer.insert_line(6, "z").is_err());
+    });
+    fixture_test!(
+        "test_insert_line_terminated",
+        "a\n",
+        |_, _, mut file_writer, _| {
+            file_writer.insert_line(1, "b").unwrap();
+            assert_eq!(file_writer.bytes(), b"a\nb\n");
+        }
+    );
+}
+
+#[test]
+fn test_delete_lines() {
+    fixture_test!(
+        "test_delete_lines",
+        "a\nb\nc\nd\n",
+        |_, _, mut file_writer, _| {
+            file_writer.delete_lines(1..3).unwrap();
+            assert_eq!(file_writer.bytes(), b"a\nd\n");
+            file_writer.delete_lines(..=0).unwrap();
+            assert_eq!(file_writer.bytes(), b"d\n");
+            assert!(file_writer.delete_lines(0..2).is_err());
+            file_writer.delete_lines(..).unwrap();
+            assert!(file_writer.is_empty());
+        }
+    );
+}
+
+#[test]
+fn test_retain_lines() {
+    fixture_test!(
+        "test_retain_lines",
+        "# a\nb\n# c\n# d\ne",
+        |_, _, mut file_writer, _| {
+            let removed = file_writer
+                .retain_lines(|line| !line.starts_with(b"#"))
+                .unwrap();
+            assert_eq!(removed, 3);
+            assert_eq!(file_writer.bytes(), b"b\ne");
+        }
+    );
+}
+
+#[test]
+fn test_map_lines() {
+    fixture_test!(
+        "test_map_lines",
+        "a=1\r\nb=2\r\nc=3",
+        |_, _, mut file_writer, _| {
+            let changed = file_writer
+                .map_lines(|line| {
+                    if line.starts_with(b"b=") {
+                        Cow::Owned(b"b=20".to_vec())
+                    } else {
+                        Cow::Borrowed(line)
+                    }
+                })
+                .unwrap();
+            assert_eq!(changed, 1);
+            assert_eq!(file_writer.bytes(), b"a=1\r\nb=20\r\nc=3");
+        }
+    );
+}
+
+#[test]
+fn test_ensure_line() {
+    fixture_test!(
+        "test_ensure_line",
+        "Port 22\nPermitRootLogin yes\n",
+        |_, _, mut file_writer, _| {
+            let changed = file_writer
+                .ensure_line(
+                    "PermitRootLogin no",
+                    LineMatch::Prefix(b"PermitRootLogin "),
+                    LinePosition::End,
+                )
+                .unwrap();
+            assert!(changed);
+            assert_eq!(file_writer.bytes(), b"Port 22\nPermitRootLogin no\n");
+
+            let changed = file_writer
+                .ensure_line(
+                    "PermitRootLogin no",
+                    LineMatch::Prefix(b"PermitRootLogin "),
+                    LinePosition::End,
+                )
+                .unwrap();
+            assert!(!changed);
+
+            let changed = file_writer
+                .ensure_line(
+                    "Protocol 2",
+                    LineMatch::Exact(b"Protocol 2"),
+                    LinePosition::After(LineMatch::Prefix(b"Port ")),
+                )
+                .unwrap();
+            assert!(changed);
+            assert_eq!(
+                file_writer.bytes(),
+                b"Port 22\nProtocol 2\nPermitRootLogin no\n"
+            );
+
+            let changed = file_writer
+                .ensure_line(
+                    "# managed",
+                    LineMatch::Exact(b"# managed"),
+                    LinePosition::Start,
+                )
+                .unwrap();
+            assert!(changed);
+            assert_eq!(
+                file_writer.bytes(),
+                b"# managed\nPort 22\nProtocol 2\nPermitRootLogin no\n"
+            );
+        }
+    );
+}
+
+#[test]
+fn test_ensure_line_existing() {
+    fixture_test!(
+        "test_ensure_line_existing",
+        "#PermitRootLogin yes\n",
+        |_, _, mut file_writer, _| {
+            for expected in [true, false] {
+                let changed = file_writer
+                    .ensure_line(
+                        "PermitRootLogin no",
+                        LineMatch::Prefix(b"#PermitRootLogin"),
+                        LinePosition::End,
+                    )
+                    .unwrap();
+                assert_eq!(changed, expected);
+            }
+            assert_eq!(file_writer.bytes(), b"PermitRootLogin no\n");
+
+            file_writer
+                .overwrite("Port 22\nPermitRootLogin no\n")
+                .unwrap();
+            for _ in 0..2 {
+                let changed = file_writer
+                    .ensure_line(
+                        "PermitRootLogin no",
+                        LineMatch::Prefix(b"#PermitRootLogin"),
+                        LinePosition::End,
+                    )
+                    .unwrap();
+                assert!(!changed);
+            }
+            assert_eq!(file_writer.bytes(), b"Port 22\nPermitRootLogin no\n");
+        }
+    );
+}
+
+#[cfg(feature = "regex")]
+#[test]
+fn test_ensure_line_regex() {
+    fixture_test!(
+        "test_ensure_line_regex",
+        "a\r\nb\r\n",
+        |_, _, mut file_writer, _| {
+            let regex = regex::bytes::Regex::new(r"^#?\s*a$").unwrap();
+            let changed = file_writer
+                .ensure_line(
+                    "c",
+                    LineMatch::Regex(&regex),
+                    LinePosition::Before(LineMatch::Exact(b"b")),
+                )
+                .unwrap();
+            assert!(changed);
+            assert_eq!(file_writer.bytes(), b"c\r\nb\r\n");
+        }
+    );
+}
+
+#[test]
+fn test_upsert_block() {
+    fixture_test!("test_upsert_block", "keep\n", |_, _, mut file_writer, _| {
+        assert!(file_writer
+            .upsert_block("# BEGIN", "# END", "a = 1\nb = 2")
+            .unwrap());
+        assert_eq!(file_writer.bytes(), b"keep\n# BEGIN\na = 1\nb = 2\n# END\n");
+        assert!(!file_writer
+            .upsert_block("# BEGIN", "# END", "a = 1\nb = 2\n")
+            .unwrap());
+        file_writer.append("tail\n").unwrap();
+        assert!(file_writer
+            .upsert_block("# BEGIN", "# END", "c = 3")
+            .unwrap());
+        assert_eq!(file_writer.bytes(), b"keep\n# BEGIN\nc = 3\n# END\ntail\n");
+        assert!(file_writer.remove_block("# BEGIN", "# END").unwrap());
+        assert_eq!(file_writer.bytes(), b"keep\ntail\n");
+        assert!(!file_writer.remove_block("# BEGIN", "# END").unwrap());
+    });
+    fixture_test!(
+        "test_upsert_block_unterminated",
+        "# BEGIN\nx",
+        |_, _, mut file_writer, _| {
+            assert!(file_writer.upsert_block("# BEGIN", "# END", "y").is_err());
+        }
+    );
+    fixture_test!(
+        "test_upsert_block_crlf",
+        "keep\r\n",
+        |_, _, mut file_writer, _| {
+            assert!(file_writer
+                .upsert_block("# BEGIN", "# END", "x\ny")
+                .unwrap());
+            assert_eq!(
+                file_writer.bytes(),
+                b"keep\r\n# BEGIN\r\nx\r\ny\r\n# END\r\n"
+            );
+            assert!(!file_writer
+                .upsert_block("# BEGIN", "# END", "x\r\ny\n")
+                .unwrap());
+            assert!(file_writer.upsert_block("# BEGIN", "# END", "z\n").unwrap());
+            assert_eq!(file_writer.bytes(), b"keep\r\n# BEGIN\r\nz\r\n# END\r\n");
+        }
+    );
+}
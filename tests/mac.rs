@@ -0,0 +1,62 @@
+#![cfg(all(feature = "mac", feature = "sha3_256"))]
+#[macro_use]
+mod common;
+
+use hex_literal::hex;
+use hmac::{Hmac, Mac};
+
+#[test]
+fn test_mac_with() {
+    fixture_test!(
+        "test_mac_with",
+        "what do ya want for nothing?",
+        |_, _, _, file_reader| {
+            let expected = hex!("5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843");
+            let tag = file_reader.mac_with::<Hmac<sha2::Sha256>>(b"Jefe").unwrap();
+            assert_eq!(tag[..], expected);
+            assert!(file_reader.verify_mac_with::<Hmac<sha2::Sha256>>(b"Jefe", &expected));
+            assert!(!file_reader.verify_mac_with::<Hmac<sha2::Sha256>>(b"Jeff", &expected));
+            assert!(!file_reader.verify_mac_with::<Hmac<sha2::Sha256>>(b"Jefe", &expected[..31]));
+        }
+    );
+}
+
+#[test]
+fn test_hmac_sha3_256() {
+    fixture_test!(
+        "test_hmac_sha3_256",
+        "file contents",
+        |_, _, _, file_reader| {
+            let mut mac = Hmac::<sha3::Sha3_256>::new_from_slice(b"secret").unwrap();
+            mac.update(b"file contents");
+            let expected = mac.finalize().into_bytes();
+            let tag = file_reader.hmac_sha3_256(b"secret");
+            assert_eq!(tag, expected);
+            assert!(file_reader.verify_hmac_sha3_256(b"secret", &tag));
+            assert!(!file_reader.verify_hmac_sha3_256(b"other", &tag));
+        }
+    );
+}
+
+#[test]
+fn test_kmac256() {
+    // NIST SP 800-185 KMAC256 sample #4.
+    let key = hex!("404142434445464748494A4B4C4D4E4F505152535455565758595A5B5C5D5E5F");
+    let expected = hex!(
+        "20C570C31346F703C9AC36C61C03CB64C3970D0CFC787E9B79599D273A68D2F7"
+        "F69D4CC3DE9D104A351689F27CF6F5951F0103F33F4F24871024D9C27773A8DD"
+    );
+    fixture_test!("test_kmac256", [0u8, 1, 2, 3], |_, _, _, file_reader| {
+        let customization = b"My Tagged Application";
+        assert_eq!(file_reader.kmac256(&key, customization, 64), expected);
+        assert!(file_reader.verify_kmac256(&key, customization, &expected, 64));
+        assert!(!file_reader.verify_kmac256(&key, b"", &expected, 64));
+        assert!(!file_reader.verify_kmac256(&key, customization, &expected[..32], 64));
+        assert!(!file_reader.verify_kmac256(&key, customization, &expected[..1], 64));
+        assert!(!file_reader.verify_kmac256(&key, customization, &[], 64));
+        assert!(!file_reader.verify_kmac256(&key, customization, &[], 0));
+        let short = file_reader.kmac256(&key, customization, 32);
+        assert!(file_reader.verify_kmac256(&key, customization, &short, 32));
+        assert!(!file_reader.verify_kmac256(&key, customization, &short, 64));
+    });
+}
@@ -0,0 +1,139 @@
+#![cfg(feature = "manifest")]
+use file_rw::{
+    manifest::{ManifestAlgorithm, ManifestStatus, ManifestStyle},
+    utils::{overwrite, read_to_string, verify_manifest, write_manifest, write_manifest_with},
+};
+use std::{io, path::PathBuf};
+use tempfile::tempdir;
+
+const HELLO_SHA256: &str = "185f8db32271fe25f561a6fc938b2e264306ec304eda518007d1764826381969";
+
+macro_rules! manifest_test {
+    (|$tempdir:ident, $paths:ident| $block:block) => {{
+        let $tempdir = tempdir().unwrap();
+        overwrite($tempdir.path().join("hello.txt"), "Hello").unwrap();
+        overwrite($tempdir.path().join("data.bin"), [0u8, 1, 2]).unwrap();
+        let $paths = vec![
+            $tempdir.path().join("hello.txt"),
+            $tempdir.path().join("data.bin"),
+        ];
+        $block
+    }};
+}
+
+#[test]
+fn test_write_manifest() {
+    manifest_test!(|tempdir, paths| {
+        let manifest_path = tempdir.path().join("SHA256SUMS");
+        write_manifest(&paths, ManifestAlgorithm::Sha256, &manifest_path).unwrap();
+        let manifest = read_to_string(&manifest_path).unwrap();
+        assert_eq!(
+            manifest.lines().next().unwrap(),
+            format!("{}  {}", HELLO_SHA256, paths[0].display())
+        );
+        let report = verify_manifest(&manifest_path).unwrap();
+        assert_eq!(report.len(), 2);
+        assert!(report.iter().all(|entry| entry.status == ManifestStatus::Ok
+            && entry.algorithm == ManifestAlgorithm::Sha256));
+
+        overwrite(&paths[1], "changed").unwrap();
+        std::fs::remove_file(&paths[0]).unwrap();
+        let statuses = verify_manifest(&manifest_path)
+            .unwrap()
+            .into_iter()
+            .map(|entry| entry.status)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            statuses,
+            vec![ManifestStatus::Missing, ManifestStatus::Mismatch]
+        );
+    });
+}
+
+#[test]
+fn test_write_manifest_styles() {
+    manifest_test!(|tempdir, paths| {
+        for (style, algorithm) in [
+            (ManifestStyle::GnuBinary, ManifestAlgorithm::Blake2b512),
+            (ManifestStyle::Bsd, ManifestAlgorithm::Sha512),
+            (ManifestStyle::Bsd, ManifestAlgorithm::Sha3_256),
+        ] {
+            let manifest_path = tempdir.path().join("manifest.b2");
+            write_manifest_with(&paths, algorithm, style, &manifest_path).unwrap();
+            let report = verify_manifest(&manifest_path).unwrap();
+            assert!(report
+                .iter()
+                .all(|entry| entry.status == ManifestStatus::Ok && entry.algorithm == algorithm));
+        }
+        let manifest_path = tempdir.path().join("manifest.txt");
+        write_manifest_with(
+            &paths[..1],
+            ManifestAlgorithm::Sha256,
+            ManifestStyle::Bsd,
+            &manifest_path,
+        )
+        .unwrap();
+        assert_eq!(
+            read_to_string(&manifest_path).unwrap(),
+            format!("SHA256 ({}) = {}\n", paths[0].display(), HELLO_SHA256)
+        );
+    });
+}
+
+#[test]
+fn test_verify_manifest_relative() {
+    manifest_test!(|tempdir, _paths| {
+        overwrite(tempdir.path().join("odd\\name.txt"), "Hello").unwrap();
+        let manifest_path = tempdir.path().join("SHA256SUMS");
+        let manifest = format!(
+            "# checksums\n{hash} *hello.txt\n\\{hash}  odd\\\\name.txt\n{hash}  gone.txt\n",
+            hash = HELLO_SHA256.to_uppercase()
+        );
+        overwrite(&manifest_path, manifest).unwrap();
+        let report = verify_manifest(&manifest_path).unwrap();
+        let report = report
+            .into_iter()
+            .map(|entry| (entry.path, entry.status))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            report,
+            vec![
+                (PathBuf::from("hello.txt"), ManifestStatus::Ok),
+                (PathBuf::from("odd\\name.txt"), ManifestStatus::Ok),
+                (PathBuf::from("gone.txt"), ManifestStatus::Missing),
+            ]
+        );
+
+        overwrite(&manifest_path, "not a checksum line\n").unwrap();
+        assert_eq!(
+            verify_manifest(&manifest_path).unwrap_err().kind(),
+            io::ErrorKind::InvalidData
+        );
+    });
+}
+
+#[test]
+fn test_verify_manifest_guess() {
+    manifest_test!(|tempdir, paths| {
+        for (name, algorithm) in [
+            ("lib2-sha256sums", ManifestAlgorithm::Sha256),
+            ("SHA256SUMS.web2", ManifestAlgorithm::Sha256),
+            ("b2-release.txt", ManifestAlgorithm::Sha256),
+            ("B2SUMS", ManifestAlgorithm::Blake2b512),
+            ("release.b2", ManifestAlgorithm::Blake2b512),
+            ("web2.sums", ManifestAlgorithm::Sha512),
+        ] {
+            let manifest_path = tempdir.path().join(name);
+            write_manifest(&paths, algorithm, &manifest_path).unwrap();
+            let report = verify_manifest(&manifest_path).unwrap();
+            assert!(
+                report
+                    .iter()
+                    .all(|entry| entry.status == ManifestStatus::Ok
+                        && entry.algorithm == algorithm),
+                "{}",
+                name
+            );
+        }
+    });
+}
@@ -0,0 +1,126 @@
+#![cfg(feature = "sha3_256")]
+#[macro_use]
+mod common;
+
+use file_rw::merkle::{sidecar_path, MerkleTree};
+use sha3::{Sha3_256, Sha3_512};
+use std::io;
+
+fn dataset() -> Vec<u8> {
+    (0..1000u32).map(|n| (n * 7 % 251) as u8).collect()
+}
+
+#[test]
+fn test_merkle_tree() {
+    fixture_test!(
+        "test_merkle_tree",
+        dataset(),
+        |_, _, mut file_writer, file_reader| {
+            let tree = file_reader.merkle_tree::<Sha3_256>(64).unwrap();
+            assert_eq!(tree.chunk_count(), 16);
+            assert_eq!(tree.chunk_range(15), Some(960..1000));
+            assert_eq!(tree, MerkleTree::new(&dataset(), 64).unwrap());
+            assert_ne!(
+                tree.root(),
+                MerkleTree::<Sha3_256>::new(&dataset(), 32).unwrap().root()
+            );
+            assert!(file_reader.verify_range(&tree, 100..300).unwrap());
+            assert!(file_reader.verify_range(&tree, 1000..1000).unwrap());
+            assert_eq!(
+                file_reader
+                    .verify_range(&tree, 900..1001)
+                    .unwrap_err()
+                    .kind(),
+                io::ErrorKind::UnexpectedEof
+            );
+
+            file_writer.write_to_offset([0xff], 500);
+            assert!(file_reader.verify_range(&tree, 0..448).unwrap());
+            assert!(!file_reader.verify_range(&tree, 400..600).unwrap());
+            let changed = file_reader.merkle_tree::<Sha3_256>(64).unwrap();
+            assert_eq!(tree.diff_chunks(&changed).unwrap(), vec![7]);
+            assert!(tree.diff_chunks(&tree).unwrap().is_empty());
+            assert_eq!(
+                tree.diff_chunks(&MerkleTree::new(&dataset(), 32).unwrap())
+                    .unwrap_err()
+                    .kind(),
+                io::ErrorKind::InvalidInput
+            );
+        }
+    );
+}
+
+#[test]
+fn test_merkle_diff_lengths() {
+    let mut longer = dataset();
+    longer.extend_from_slice(&[1; 100]);
+    longer[10] ^= 1;
+    let tree = MerkleTree::<Sha3_256>::new(&dataset(), 100).unwrap();
+    let longer_tree = MerkleTree::<Sha3_256>::new(&longer, 100).unwrap();
+    assert_eq!(tree.diff_chunks(&longer_tree).unwrap(), vec![0, 10]);
+    assert_eq!(longer_tree.diff_chunks(&tree).unwrap(), vec![0, 10]);
+    let empty = MerkleTree::<Sha3_256>::new(&[], 100).unwrap();
+    assert_eq!(empty.chunk_count(), 1);
+}
+
+#[test]
+fn test_merkle_proof() {
+    let data = dataset();
+    for chunk_size in [64, 100, 333, 1000] {
+        let tree = MerkleTree::<Sha3_256>::new(&data, chunk_size).unwrap();
+        for index in 0..tree.chunk_count() {
+            let proof = tree.proof(index).unwrap();
+            let chunk = &data[tree.chunk_range(index).unwrap()];
+            assert!(proof.verify(tree.root(), chunk));
+            assert!(!proof.verify(tree.root(), &chunk[1..]));
+        }
+        assert!(tree.proof(tree.chunk_count()).is_none());
+    }
+}
+
+#[test]
+fn test_merkle_save_load() {
+    fixture_test!(
+        "test_merkle_save_load",
+        dataset(),
+        |tempdir, test_file_path, _, file_reader| {
+            let tree = file_reader.merkle_tree::<Sha3_256>(128).unwrap();
+            let sidecar = sidecar_path(test_file_path);
+            assert_eq!(sidecar, tempdir.path().join("test_merkle_save_load.merkle"));
+            tree.save(&sidecar).unwrap();
+            assert_eq!(MerkleTree::<Sha3_256>::load(&sidecar).unwrap(), tree);
+            assert_eq!(
+                MerkleTree::<Sha3_512>::load(&sidecar).unwrap_err().kind(),
+                io::ErrorKind::InvalidData
+            );
+            assert_eq!(
+                MerkleTree::<Sha3_256>::load(test_file_path)
+                    .unwrap_err()
+                    .kind(),
+                io::ErrorKind::InvalidData
+            );
+        }
+    );
+}
+
+#[test]
+fn test_merkle_load_corrupt() {
+    let mut header = b"FRWMRKL1".to_vec();
+    header.extend_from_slice(&1u64.to_le_bytes());
+    header.extend_from_slice(&u64::MAX.to_le_bytes());
+    header.extend_from_slice(&32u32.to_le_bytes());
+    header.extend_from_slice(&u64::MAX.to_le_bytes());
+    header.extend_from_slice(&[0; 32]);
+    fixture_test!(
+        "test_merkle_load_corrupt.merkle",
+        header,
+        |_, test_file_path, _, _| {
+            assert_eq!(
+                MerkleTree::<Sha3_256>::load(test_file_path)
+                    .unwrap_err()
+                    .kind(),
+                io::ErrorKind::InvalidData
+            );
+        }
+    );
+}
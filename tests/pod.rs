@@ -0,0 +1,63 @@
+#![cfg(feature = "bytemuck")]
+#[macro_use]
+mod common;
+
+use bytemuck::{Pod, Zeroable};
+use std::io;
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+struct Header {
+    magic: [u8; 4],
+    version: u32,
+    count: u64,
+}
+
+#[test]
+fn test_as_slice_of() {
+    let samples = [0.5f32, -1.0, 2.25];
+    fixture_test!(
+        "test_as_slice_of.f32",
+        bytemuck::cast_slice::<f32, u8>(&samples),
+        |_, _, mut file_writer, file_reader| {
+            assert_eq!(file_reader.as_slice_of::<f32>().unwrap(), &samples);
+            file_writer.as_mut_slice_of::<f32>().unwrap()[1] = 4.0;
+            assert_eq!(file_reader.as_slice_of::<f32>().unwrap(), &[0.5, 4.0, 2.25]);
+            let err = file_reader.as_slice_of::<u64>().unwrap_err();
+            assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        }
+    );
+}
+
+#[test]
+fn test_view_at() {
+    let header = Header {
+        magic: *b"FRW0",
+        version: 2,
+        count: 7,
+    };
+    let mut bytes = bytemuck::bytes_of(&header).to_vec();
+    bytes.extend_from_slice(&[1, 2, 3]);
+    fixture_test!(
+        "test_view_at.bin",
+        &bytes,
+        |_, _, mut file_writer, file_reader| {
+            assert_eq!(file_reader.view_at::<Header>(0).unwrap(), &header);
+            assert_eq!(*file_reader.view_at::<u32>(4).unwrap(), 2);
+            file_writer.view_mut_at::<Header>(0).unwrap().count += 1;
+            assert_eq!(file_writer.view_at::<Header>(0).unwrap().count, 8);
+            assert_eq!(
+                file_reader.view_at::<u32>(2).unwrap_err().kind(),
+                io::ErrorKind::InvalidData
+            );
+            assert_eq!(
+                file_reader.view_at::<Header>(8).unwrap_err().kind(),
+                io::ErrorKind::UnexpectedEof
+            );
+            assert_eq!(
+                file_reader.view_at::<u8>(usize::MAX).unwrap_err().kind(),
+                io::ErrorKind::UnexpectedEof
+            );
+        }
+    );
+}
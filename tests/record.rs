@@ -0,0 +1,139 @@
+#![cfg(feature = "bytemuck")]
+use bytemuck::{Pod, Zeroable};
+use file_rw::{record::RecordFile, FileWriter};
+use std::io;
+use tempfile::tempdir;
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+struct Sample {
+    timestamp: u64,
+    value: f32,
+    sensor: u16,
+    flags: u16,
+}
+
+fn sample(timestamp: u64) -> Sample {
+    Sample {
+        timestamp,
+        value: timestamp as f32 / 2.0,
+        sensor: 1,
+        flags: 0,
+    }
+}
+
+#[test]
+fn test_record_file_crud() {
+    let tempdir = tempdir().unwrap();
+    let test_file_path = tempdir.path().join("test_record_file_crud.rec");
+    let mut records =
+        RecordFile::<Sample, _>::open(FileWriter::open(&test_file_path).unwrap()).unwrap();
+    assert!(records.is_empty());
+    for timestamp in 0..20 {
+        assert_eq!(
+            records.push(&sample(timestamp)).unwrap(),
+            timestamp as usize
+        );
+    }
+    assert_eq!(records.len(), 20);
+    assert_eq!(records.capacity(), 32);
+    assert_eq!(records.get(3), Some(&sample(3)));
+    assert_eq!(records.get(20), None);
+
+    assert!(records.update(3, &sample(300)));
+    assert_eq!(records.get(3), Some(&sample(300)));
+    assert_eq!(records.remove(5), Some(sample(5)));
+    assert_eq!(records.remove(7), Some(sample(7)));
+    assert_eq!(records.remove(7), None);
+    assert!(!records.update(7, &sample(7)));
+    assert_eq!(records.get(5), None);
+    assert_eq!(records.len(), 18);
+
+    assert_eq!(records.push(&sample(70)).unwrap(), 7);
+    assert_eq!(records.push(&sample(50)).unwrap(), 5);
+    assert_eq!(records.push(&sample(20)).unwrap(), 20);
+    let file_len = records.writer().len();
+    drop(records);
+
+    let records =
+        RecordFile::<Sample, _>::open(FileWriter::open(&test_file_path).unwrap()).unwrap();
+    assert_eq!(records.writer().len(), file_len);
+    assert_eq!(records.len(), 21);
+    let timestamps = records
+        .iter()
+        .map(|(index, record)| (index, record.timestamp))
+        .take(8)
+        .collect::<Vec<_>>();
+    assert_eq!(
+        timestamps,
+        vec![
+            (0, 0),
+            (1, 1),
+            (2, 2),
+            (3, 300),
+            (4, 4),
+            (5, 50),
+            (6, 6),
+            (7, 70)
+        ]
+    );
+}
+
+#[test]
+fn test_record_file_mismatch() {
+    let tempdir = tempdir().unwrap();
+    let test_file_path = tempdir.path().join("test_record_file_mismatch.rec");
+    let mut records =
+        RecordFile::<u32, _>::open(FileWriter::open(&test_file_path).unwrap()).unwrap();
+    records.push(&1).unwrap();
+    drop(records);
+    let err = RecordFile::<Sample, _>::open(FileWriter::open(&test_file_path).unwrap())
+        .map(|_| ())
+        .unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+
+    let mut file_writer = FileWriter::open(&test_file_path).unwrap();
+    file_writer
+        .overwrite(b"not a record file, just some text!!!!!!!!")
+        .unwrap();
+    let err = RecordFile::<u32, _>::open(file_writer)
+        .map(|_| ())
+        .unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+}
+
+#[test]
+fn test_record_file_corrupt_free_list() {
+    const FREE_HEAD_OFFSET: usize = 32;
+    const SLOT_1_OFFSET: usize = 40 + 16;
+
+    let tempdir = tempdir().unwrap();
+    let test_file_path = tempdir
+        .path()
+        .join("test_record_file_corrupt_free_list.rec");
+    let mut records =
+        RecordFile::<u32, _>::open(FileWriter::open(&test_file_path).unwrap()).unwrap();
+    for value in 0..3 {
+        records.push(&value).unwrap();
+    }
+    records.remove(1).unwrap();
+    let mut file_writer = records.into_writer();
+
+    for free_head in [3, 99, u64::MAX] {
+        file_writer.write_to_offset(free_head.to_ne_bytes(), FREE_HEAD_OFFSET);
+        let err = RecordFile::<u32, _>::open(file_writer)
+            .map(|_| ())
+            .unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        file_writer = FileWriter::open(&test_file_path).unwrap();
+    }
+    file_writer.write_to_offset(1u64.to_ne_bytes(), FREE_HEAD_OFFSET);
+
+    for state in [u64::MAX, 1000] {
+        file_writer.write_to_offset(state.to_ne_bytes(), SLOT_1_OFFSET);
+        let mut records = RecordFile::<u32, _>::open(file_writer).unwrap();
+        let err = records.push(&7).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        file_writer = records.into_writer();
+    }
+}
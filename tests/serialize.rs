@@ -0,0 +1,186 @@
+#![cfg(feature = "serde")]
+#[macro_use]
+mod common;
+
+use file_rw::FileReader;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Config {
+    name: String,
+    port: u16,
+    tags: Vec<String>,
+}
+
+#[derive(Debug, PartialEq, Deserialize)]
+struct BorrowedConfig<'a> {
+    name: &'a str,
+    port: u16,
+}
+
+fn config() -> Config {
+    Config {
+        name: "file_rw".to_string(),
+        port: 8080,
+        tags: vec!["mmap".to_string(), "io".to_string()],
+    }
+}
+
+#[test]
+fn test_serialize_json() {
+    fixture_test!(
+        "test_serialize_json.json",
+        "",
+        |_, test_file_path, mut file_writer, _| {
+            file_writer
+                .overwrite("previous contents that are longer")
+                .unwrap();
+            file_writer.serialize_json(&config(), false).unwrap();
+            assert_eq!(
+                file_writer.bytes(),
+                br#"{"name":"file_rw","port":8080,"tags":["mmap","io"]}"#
+            );
+            file_writer.serialize_json(&config(), true).unwrap();
+            assert!(file_writer
+                .bytes()
+                .starts_with(b"{\n  \"name\": \"file_rw\""));
+
+            let reader = FileReader::open(&test_file_path).unwrap();
+            assert_eq!(reader.deserialize_json::<Config>().unwrap(), config());
+            let borrowed = reader.deserialize_json::<BorrowedConfig>().unwrap();
+            assert_eq!(borrowed.name, "file_rw");
+            assert!(reader
+                .as_slice()
+                .as_ptr_range()
+                .contains(&borrowed.name.as_ptr()));
+            assert_eq!(
+                reader.deserialize_json::<u8>().unwrap_err().kind(),
+                std::io::ErrorKind::InvalidData
+            );
+        }
+    );
+}
+
+#[cfg(feature = "toml")]
+#[test]
+fn test_serialize_toml() {
+    fixture_test!(
+        "test_serialize_toml.toml",
+        "",
+        |_, test_file_path, mut file_writer, _| {
+            file_writer.serialize_toml(&config(), false).unwrap();
+            let reader = FileReader::open(&test_file_path).unwrap();
+            assert_eq!(reader.deserialize_toml::<Config>().unwrap(), config());
+        }
+    );
+}
+
+#[cfg(feature = "bincode")]
+#[test]
+fn test_serialize_bincode() {
+    fixture_test!(
+        "test_serialize_bincode.bin",
+        "",
+        |_, test_file_path, mut file_writer, _| {
+            file_writer.serialize_bincode(&config()).unwrap();
+            let reader = FileReader::open(&test_file_path).unwrap();
+            assert_eq!(reader.deserialize_bincode::<Config>().unwrap(), config());
+            let borrowed = reader.deserialize_bincode::<BorrowedConfig>().unwrap();
+            assert_eq!(borrowed.name, "file_rw");
+        }
+    );
+}
+
+#[cfg(feature = "postcard")]
+#[test]
+fn test_serialize_postcard() {
+    fixture_test!(
+        "test_serialize_postcard.bin",
+        "",
+        |_, test_file_path, mut file_writer, _| {
+            file_writer.serialize_postcard(&config()).unwrap();
+            let reader = FileReader::open(&test_file_path).unwrap();
+            assert_eq!(reader.deserialize_postcard::<Config>().unwrap(), config());
+            let borrowed = reader.deserialize_postcard::<BorrowedConfig>().unwrap();
+            assert_eq!(borrowed.port, 8080);
+        }
+    );
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Event<'a> {
+    id: u32,
+    kind: &'a str,
+}
+
+#[test]
+fn test_json_lines() {
+    fixture_test!(
+        "test_json_lines.ndjson",
+        "",
+        |_, test_file_path, mut file_writer, _| {
+            file_writer
+                .overwrite("{\"id\":1,\"kind\":\"start\"}")
+                .unwrap();
+            file_writer
+                .append_json_line(&Event {
+                    id: 2,
+                    kind: "tick",
+                })
+                .unwrap();
+            file_writer.append("\r\n{\"id\":\"bad\"}\n").unwrap();
+            file_writer
+                .append_json_line(&Event {
+                    id: 4,
+                    kind: "stop",
+                })
+                .unwrap();
+            assert_eq!(
+                file_writer.bytes(),
+                b"{\"id\":1,\"kind\":\"start\"}\n{\"id\":2,\"kind\":\"tick\"}\n\r\n{\"id\":\"bad\"}\n{\"id\":4,\"kind\":\"stop\"}\n"
+            );
+
+            let reader = FileReader::open(&test_file_path).unwrap();
+            let records = reader.json_lines::<Event>().collect::<Vec<_>>();
+            assert_eq!(records.len(), 4);
+            assert_eq!(
+                records[0].as_ref().unwrap(),
+                &Event {
+                    id: 1,
+                    kind: "start"
+                }
+            );
+            assert_eq!(
+                records[1].as_ref().unwrap(),
+                &Event {
+                    id: 2,
+                    kind: "tick"
+                }
+            );
+            let error = records[2].as_ref().unwrap_err();
+            assert_eq!(error.line(), 4);
+            assert_eq!(error.offset(), 49);
+            assert_eq!(
+                records[3].as_ref().unwrap(),
+                &Event {
+                    id: 4,
+                    kind: "stop"
+                }
+            );
+
+            #[cfg(feature = "rayon")]
+            {
+                let parallel = reader.par_json_lines::<Event>();
+                assert_eq!(parallel.len(), 4);
+                assert_eq!(
+                    parallel[3].as_ref().unwrap(),
+                    &Event {
+                        id: 4,
+                        kind: "stop"
+                    }
+                );
+                assert_eq!(parallel[2].as_ref().unwrap_err().line(), 4);
+            }
+        }
+    );
+}
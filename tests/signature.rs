@@ -0,0 +1,75 @@
+#![cfg(feature = "signature")]
+#[macro_use]
+mod common;
+
+use ed25519_dalek::{Signature, SigningKey};
+use file_rw::{
+    signature::{read_signature, signature_path, write_signature, SignatureMode},
+    utils::overwrite,
+};
+use std::io;
+
+#[test]
+fn test_sign_verify() {
+    fixture_test!("release.tar", "artifact", |_, _, _, file_reader| {
+        let key = SigningKey::from_bytes(&[7; 32]);
+        let signature = file_reader.sign(&key);
+        assert!(file_reader.verify(&key.verifying_key(), &signature));
+        let other_key = SigningKey::from_bytes(&[8; 32]).verifying_key();
+        assert!(!file_reader.verify(&other_key, &signature));
+
+        let mode = SignatureMode::Prehashed(Some(b"release"));
+        let prehashed = file_reader.sign_with(&key, mode).unwrap();
+        assert_ne!(prehashed, signature);
+        assert!(file_reader.verify_with(&key.verifying_key(), &prehashed, mode));
+        assert!(!file_reader.verify_with(
+            &key.verifying_key(),
+            &prehashed,
+            SignatureMode::Prehashed(None)
+        ));
+        assert!(!file_reader.verify_with(&key.verifying_key(), &prehashed, SignatureMode::Pure));
+        assert_eq!(
+            file_reader
+                .sign_with(&key, SignatureMode::Prehashed(Some(&[0; 256])))
+                .unwrap_err()
+                .kind(),
+            io::ErrorKind::InvalidInput
+        );
+    });
+}
+
+#[test]
+fn test_detached_signature() {
+    fixture_test!("release.tar", "artifact", |tempdir, _, _, file_reader| {
+        let key = SigningKey::from_bytes(&[7; 32]);
+        let sig_path = file_reader
+            .sign_detached(&key, SignatureMode::Pure)
+            .unwrap();
+        assert_eq!(sig_path, tempdir.path().join("release.tar.sig"));
+        assert_eq!(sig_path, signature_path(tempdir.path().join("release.tar")));
+        assert!(file_reader
+            .verify_detached(&key.verifying_key(), SignatureMode::Pure)
+            .unwrap());
+        let signature = read_signature(&sig_path).unwrap();
+        assert_eq!(signature, file_reader.sign(&key));
+
+        let hex_path = tempdir.path().join("hex.sig");
+        let hex = signature
+            .to_bytes()
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect::<String>();
+        overwrite(&hex_path, format!("{}\n", hex)).unwrap();
+        assert_eq!(read_signature(&hex_path).unwrap(), signature);
+
+        write_signature(&Signature::from_bytes(&[0; 64]), &sig_path).unwrap();
+        assert!(!file_reader
+            .verify_detached(&key.verifying_key(), SignatureMode::Pure)
+            .unwrap());
+        overwrite(&sig_path, "not a signature").unwrap();
+        assert_eq!(
+            read_signature(&sig_path).unwrap_err().kind(),
+            io::ErrorKind::InvalidData
+        );
+    });
+}
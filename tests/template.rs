@@ -0,0 +1,92 @@
+#![cfg(feature = "search")]
+#[macro_use]
+mod common;
+
+use file_rw::template::{Delimiters, Escape, OnMissing};
+use std::collections::HashMap;
+
+fn values() -> HashMap<&'static str, &'static str> {
+    HashMap::from([("name", "api"), ("replicas", "3"), ("quote", "it's <b>")])
+}
+
+#[test]
+fn test_render_placeholders() {
+    fixture_test!(
+        "test_render_placeholders",
+        "name: {{ name }}\nreplicas: {{replicas}}\nimage: {{ image }}\nopen: {{ name",
+        |_, _, mut file_writer, _| {
+            let err = file_writer
+                .render_placeholders(&values(), Delimiters::default(), OnMissing::Error)
+                .unwrap_err();
+            assert_eq!(err.kind(), std::io::ErrorKind::NotFound);
+            let rendered = file_writer
+                .render_placeholders(&values(), Delimiters::default(), OnMissing::Leave)
+                .unwrap();
+            assert_eq!(rendered, 2);
+            assert_eq!(
+                file_writer.bytes(),
+                b"name: api\nreplicas: 3\nimage: {{ image }}\nopen: {{ name"
+            );
+            file_writer
+                .render_placeholders(&values(), Delimiters::default(), OnMissing::Empty)
+                .unwrap();
+            assert_eq!(
+                file_writer.bytes(),
+                b"name: api\nreplicas: 3\nimage: \nopen: {{ name"
+            );
+        }
+    );
+}
+
+#[test]
+fn test_render_placeholders_escaped() {
+    fixture_test!(
+        "test_render_placeholders_escaped",
+        "<p>${quote}</p> echo ${quote}",
+        |_, _, mut file_writer, _| {
+            let delimiters = Delimiters {
+                open: b"${",
+                close: b"}",
+            };
+            file_writer
+                .render_placeholders_escaped(&values(), delimiters, OnMissing::Error, Escape::Html)
+                .unwrap();
+            assert_eq!(
+                file_writer.bytes(),
+                b"<p>it&#39;s &lt;b&gt;</p> echo it&#39;s &lt;b&gt;"
+            );
+        }
+    );
+    fixture_test!(
+        "test_render_placeholders_shell",
+        "echo {{quote}}",
+        |_, _, mut file_writer, _| {
+            file_writer
+                .render_placeholders_escaped(
+                    &values(),
+                    Delimiters::default(),
+                    OnMissing::Error,
+                    Escape::Shell,
+                )
+                .unwrap();
+            assert_eq!(file_writer.bytes(), b"echo 'it'\\''s <b>'");
+        }
+    );
+}
+
+#[test]
+fn test_render_placeholders_empty_delimiters() {
+    fixture_test!(
+        "test_render_placeholders_empty_delimiters",
+        "name: {{ name }}",
+        |_, _, mut file_writer, _| {
+            for (open, close) in [(&b""[..], &b""[..]), (b"{{", b""), (b"", b"}}")] {
+                let err = file_writer
+                    .render_placeholders(&values(), Delimiters { open, close }, OnMissing::Empty)
+                    .unwrap_err();
+                assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+            }
+            assert_eq!(file_writer.bytes(), b"name: {{ name }}");
+        }
+    );
+}
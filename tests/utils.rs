@@ -105,3 +105,29 @@ fn test_get_mmap_write() {
     let mmap = get_mmap_read(&path).unwrap();
     assert_eq!(&mmap[..], b"hello");
 }
+
+#[cfg(feature = "search")]
+#[test]
+fn test_render_template() {
+    let dir = tempdir().unwrap();
+    let src_path = dir.path().join("test_render_template.tmpl");
+    let dst_path = dir.path().join("test_render_template");
+    let mut writer = FileWriter::open(&src_path).unwrap();
+    writer
+        .overwrite("host={{ host }}\nport={{ port }}\n")
+        .unwrap();
+    let values = std::collections::HashMap::from([("host", "localhost"), ("port", "8080")]);
+    render_template(&src_path, &dst_path, &values).unwrap();
+    assert_eq!(
+        read_to_vec(&dst_path).unwrap(),
+        b"host=localhost\nport=8080\n"
+    );
+    let missing = dir.path().join("test_render_template_missing");
+    assert!(render_template(
+        &src_path,
+        &missing,
+        &std::collections::HashMap::from([("host", "x")])
+    )
+    .is_err());
+    assert!(!missing.exists());
+}